@@ -8,12 +8,44 @@ use std::{fs::File, path::PathBuf};
 #[derive(Clone, Serialize, Debug)]
 struct TemplateData {
     preimage: Preimage,
+    target: Option<Target>,
 }
 
 impl TemplateData {
-    fn new(preimage: &[u8]) -> Self {
+    fn new(preimage: &[u8], schedule: Option<Schedule>) -> Self {
         TemplateData {
             preimage: preimage.into(),
+            target: schedule.map(Into::into),
+        }
+    }
+}
+
+/// Relay chain block that a proposal is scheduled at with `Scheduler.schedule` or
+/// `Scheduler.schedule_after`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Schedule {
+    At(u32),
+    After(u32),
+}
+
+// Block the script fast-forwards to, relative to the block the preimage is dispatched in
+#[derive(Clone, Serialize, Debug)]
+struct Target {
+    block: u32,
+    relative: bool,
+}
+
+impl From<Schedule> for Target {
+    fn from(schedule: Schedule) -> Self {
+        match schedule {
+            Schedule::At(block) => Target {
+                block,
+                relative: false,
+            },
+            Schedule::After(block) => Target {
+                block,
+                relative: true,
+            },
         }
     }
 }
@@ -41,6 +73,7 @@ fn as_hex_literal(s: &[u8]) -> String {
 
 pub fn generate_chopsticks_script(
     preimage: &[u8],
+    schedule: Option<Schedule>,
     output_path: PathBuf,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut registry = Handlebars::new();
@@ -49,7 +82,7 @@ pub fn generate_chopsticks_script(
     registry.register_escape_fn(|s| -> String { s.to_string() });
 
     let template = include_str!("../templates/chopsticks-execute-upgrade.js.hbs");
-    let data = TemplateData::new(preimage);
+    let data = TemplateData::new(preimage, schedule);
     let output = registry.render_template(template, &data)?;
     let mut file = File::create(output_path)?;
     file.write_all(output.as_bytes())?;
@@ -1,3 +0,0 @@
-fn main() {
-    println!("cargo:rerun-if-changed=templates");
-}
@@ -1,2 +0,0 @@
-pub use asset_hub_kusama_runtime::runtime_types::asset_hub_kusama_runtime::RuntimeCall;
-pub use asset_hub_kusama_runtime::*;
@@ -1,4 +0,0 @@
-pub const ASSET_HUB_ID: u32 = 1000;
-pub const ASSET_HUB_API: &str = "wss://asset-hub-kusama-rpc.dwellir.com";
-pub const RELAY_API: &str = "wss://kusama-rpc.dwellir.com";
-pub const CHAIN_ID: u64 = 1;
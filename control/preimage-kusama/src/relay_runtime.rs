@@ -1 +0,0 @@
-pub use kusama_runtime::*;
@@ -1,3 +1,17 @@
+use std::process::Command;
+
+// Embed the git revision in the tool, for the provenance manifest of its JSON output
 fn main() {
+    let revision = Command::new("git")
+        .args(["describe", "--always", "--dirty", "--abbrev=40"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|revision| revision.trim().to_owned())
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=GIT_REVISION={revision}");
     println!("cargo:rerun-if-changed=templates");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/index");
 }
@@ -0,0 +1,291 @@
+use super::asset_hub_runtime;
+use super::asset_hub_runtime::foreign_assets::storage::types::{asset::Asset, metadata::Metadata};
+use super::asset_hub_types::Location;
+use super::NETWORK;
+use crate::{AssetsArgs, Context};
+use alloy_primitives::Address;
+use codec::Decode;
+use serde::Serialize;
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use subxt::{utils::H256, OnlineClient, PolkadotConfig};
+use xcm::v4::{Junction::AccountKey20, Junction::GlobalConsensus, NetworkId};
+
+/// Asset bridged from Ethereum to AssetHub
+#[derive(Debug, Serialize)]
+pub struct ForeignAsset {
+    /// `Ether`, or the address of the ERC20 contract
+    pub token: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub min_balance: u128,
+    pub is_sufficient: bool,
+    pub owner: String,
+}
+
+/// Polkadot-native asset registered on Ethereum through BridgeHub
+#[derive(Debug, Serialize)]
+pub struct NativeAsset {
+    pub token_id: H256,
+    /// Location of the asset reanchored to Ethereum
+    pub location: xcm::v4::Location,
+}
+
+// Details and metadata of a foreign asset on AssetHub, if it exists
+pub async fn query_foreign_asset(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+    location: &Location,
+) -> Result<Option<(Asset, Metadata)>, Box<dyn std::error::Error>> {
+    let storage = api.storage().at(at);
+    let Some(details) = storage
+        .fetch(
+            &asset_hub_runtime::storage()
+                .foreign_assets()
+                .asset(location),
+        )
+        .await?
+    else {
+        return Ok(None);
+    };
+    let metadata = storage
+        .fetch_or_default(
+            &asset_hub_runtime::storage()
+                .foreign_assets()
+                .metadata(location),
+        )
+        .await?;
+
+    Ok(Some((details, metadata)))
+}
+
+// Fail for an asset which already exists on AssetHub, as creating it again fails on chain
+pub async fn ensure_unregistered(
+    context: &Context,
+    location: &Location,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let asset = query_foreign_asset(
+        &context.asset_hub_api,
+        context.asset_hub_block.hash,
+        location,
+    )
+    .await?;
+    if asset.is_some() {
+        return Err("already registered on AssetHub".into());
+    }
+    Ok(())
+}
+
+// Foreign assets on AssetHub located under the bridged Ethereum network
+pub async fn foreign_assets(
+    context: &Context,
+) -> Result<Vec<ForeignAsset>, Box<dyn std::error::Error>> {
+    let storage = context
+        .asset_hub_api
+        .storage()
+        .at(context.asset_hub_block.hash);
+    let mut entries = storage
+        .iter(asset_hub_runtime::storage().foreign_assets().asset_iter())
+        .await?;
+
+    let mut assets = vec![];
+    while let Some(kv) = entries.next().await {
+        let kv = kv?;
+        // The location follows the pallet and storage prefixes and its Blake2_128Concat hash
+        let Some((location, token)) = kv.key_bytes.get(48..).and_then(ethereum_token) else {
+            continue;
+        };
+        let metadata = storage
+            .fetch_or_default(
+                &asset_hub_runtime::storage()
+                    .foreign_assets()
+                    .metadata(&location),
+            )
+            .await?;
+
+        assets.push(ForeignAsset {
+            token,
+            name: String::from_utf8_lossy(&metadata.name.0).into_owned(),
+            symbol: String::from_utf8_lossy(&metadata.symbol.0).into_owned(),
+            decimals: metadata.decimals,
+            min_balance: kv.value.min_balance,
+            is_sufficient: kv.value.is_sufficient,
+            owner: AccountId32::from(kv.value.owner.0)
+                .to_ss58check_with_version(Ss58AddressFormat::custom(NETWORK.ss58_prefix())),
+        });
+    }
+    assets.sort_by(|a, b| a.symbol.cmp(&b.symbol).then(a.token.cmp(&b.token)));
+
+    Ok(assets)
+}
+
+// Location and token of a foreign asset bridged from the Ethereum network of this network, given
+// its encoded location. Other assets, including those with locations which can't be decoded, are
+// skipped.
+fn ethereum_token(key: &[u8]) -> Option<(Location, String)> {
+    let location = Location::decode(&mut &key[..]).ok()?;
+    // The runtimes key foreign assets with v3, v4 or v5 locations, which encode the same way
+    // for assets from Ethereum
+    let xcm_location = xcm::v4::Location::decode(&mut &key[..]).ok()?;
+    let (chain_id, token) = match xcm_location.unpack() {
+        (2, [GlobalConsensus(NetworkId::Ethereum { chain_id })]) => {
+            (*chain_id, "Ether".to_string())
+        }
+        (2, [GlobalConsensus(NetworkId::Ethereum { chain_id }), AccountKey20 { key, .. }]) => {
+            (*chain_id, Address::from(*key).to_checksum(None))
+        }
+        _ => return None,
+    };
+    (chain_id == NETWORK.chain_id()).then_some((location, token))
+}
+
+// Fail for an asset listed more than once, as creating it again in the same proposal fails on
+// chain, which `ensure_unregistered` can't tell from the chain state
+pub fn ensure_unique<T: PartialEq>(ids: &[T]) -> Result<(), String> {
+    for (index, id) in ids.iter().enumerate() {
+        if let Some(first) = ids[..index].iter().position(|other| other == id) {
+            return Err(format!("Asset #{index} is listed before as asset #{first}"));
+        }
+    }
+    Ok(())
+}
+
+// Print the assets bridged from Ethereum to AssetHub, and the Polkadot-native assets registered
+// on Ethereum
+pub async fn inventory(
+    context: &Context,
+    params: &AssetsArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let foreign_assets = foreign_assets(context).await?;
+    let native_assets = super::native_assets::polkadot_native_assets(context).await?;
+
+    if params.json {
+        let output = serde_json::json!({
+            "foreign_assets": foreign_assets,
+            "polkadot_native_assets": native_assets,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!(
+        "AssetHub foreign assets (#{} {:?}):",
+        context.asset_hub_block.number, context.asset_hub_block.hash
+    );
+    print_table(
+        &[
+            "SYMBOL",
+            "NAME",
+            "DECIMALS",
+            "MIN BALANCE",
+            "SUFFICIENT",
+            "TOKEN",
+            "OWNER",
+        ],
+        foreign_assets
+            .iter()
+            .map(|asset| {
+                vec![
+                    asset.symbol.clone(),
+                    asset.name.clone(),
+                    asset.decimals.to_string(),
+                    asset.min_balance.to_string(),
+                    asset.is_sufficient.to_string(),
+                    asset.token.clone(),
+                    asset.owner.clone(),
+                ]
+            })
+            .collect(),
+    );
+    println!(
+        "BridgeHub Polkadot-native assets (#{} {:?}):",
+        context.bridge_hub_block.number, context.bridge_hub_block.hash
+    );
+    print_table(
+        &["TOKEN ID", "LOCATION"],
+        native_assets
+            .iter()
+            .map(|asset| {
+                vec![
+                    format!("{:?}", asset.token_id),
+                    format!("{:?}", asset.location),
+                ]
+            })
+            .collect(),
+    );
+
+    Ok(())
+}
+
+pub fn print_table(header: &[&str], rows: Vec<Vec<String>>) {
+    if rows.is_empty() {
+        println!("  none");
+        return;
+    }
+    let header: Vec<String> = header.iter().map(|cell| cell.to_string()).collect();
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.chars().count()).collect();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:<width$}"))
+            .collect();
+        println!("  {}", line.join("  ").trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::asset_hub_types::{get_asset_id, get_ether_id};
+    use super::*;
+    use codec::Encode;
+
+    const WETH: [u8; 20] = [
+        0xc0, 0x2a, 0xaa, 0x39, 0xb2, 0x23, 0xfe, 0x8d, 0x0a, 0x0e, 0x5c, 0x4f, 0x27, 0xea, 0xd9,
+        0x08, 0x3c, 0x75, 0x6c, 0xc2,
+    ];
+
+    fn token(location: &Location) -> Option<String> {
+        ethereum_token(&location.encode()).map(|(_, token)| token)
+    }
+
+    #[test]
+    fn decodes_ethereum_tokens() {
+        let chain_id = NETWORK.chain_id();
+        assert_eq!(token(&get_ether_id(chain_id)), Some("Ether".into()));
+        assert_eq!(
+            token(&get_asset_id(chain_id, WETH)),
+            Some("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".into())
+        );
+
+        let (location, _) = ethereum_token(&get_ether_id(chain_id).encode()).unwrap();
+        assert_eq!(location.encode(), get_ether_id(chain_id).encode());
+    }
+
+    #[test]
+    fn skips_other_assets() {
+        // Assets of another Ethereum chain
+        assert_eq!(token(&get_ether_id(NETWORK.chain_id() + 1)), None);
+        // Keys which are not Ethereum locations, or can't be decoded at all
+        let mut relay_token = get_ether_id(NETWORK.chain_id()).encode();
+        relay_token[0] = 1;
+        assert!(ethereum_token(&relay_token).is_none());
+        assert!(ethereum_token(&[0xff, 0xff, 0xff]).is_none());
+        assert!(ethereum_token(&[]).is_none());
+    }
+
+    #[test]
+    fn rejects_assets_listed_twice() {
+        assert_eq!(ensure_unique::<u8>(&[]), Ok(()));
+        assert_eq!(ensure_unique(&[1, 2, 3]), Ok(()));
+        assert_eq!(
+            ensure_unique(&[1, 2, 3, 2]),
+            Err("Asset #3 is listed before as asset #1".into())
+        );
+    }
+}
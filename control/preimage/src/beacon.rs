@@ -0,0 +1,714 @@
+use alloy_primitives::FixedBytes;
+use serde::Deserialize;
+use serde_json::json;
+use snowbridge_beacon_primitives::verify_merkle_branch;
+use sp_crypto_hashing::sha2_256;
+use std::sync::OnceLock;
+
+type CheckpointUpdate = snowbridge_beacon_primitives::CheckpointUpdate<512>;
+
+// Mainnet preset, also used by Sepolia
+const SLOTS_PER_HISTORICAL_ROOT: usize = 8192;
+const EPOCHS_PER_HISTORICAL_VECTOR: usize = 65536;
+const EPOCHS_PER_SLASHINGS_VECTOR: usize = 8192;
+const SYNC_COMMITTEE_SIZE: usize = 512;
+
+pub const SLOTS_PER_EPOCH: u64 = 32;
+
+// Indices of fields in the `BeaconState` container
+const BLOCK_ROOTS_INDEX: usize = 5;
+const CURRENT_SYNC_COMMITTEE_INDEX: usize = 22;
+
+#[derive(Debug, Deserialize)]
+struct Response<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct HeaderResponse {
+    root: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Bootstrap {
+    header: LightClientHeader,
+    current_sync_committee: SyncCommittee,
+    current_sync_committee_branch: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LightClientHeader {
+    beacon: BeaconHeader,
+}
+
+#[derive(Debug, Deserialize)]
+struct BeaconHeader {
+    slot: String,
+    proposer_index: String,
+    parent_root: String,
+    state_root: String,
+    body_root: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SyncCommittee {
+    pubkeys: Vec<String>,
+    aggregate_pubkey: String,
+}
+
+/// Build a checkpoint from a beacon node. Without a slot or block root, the latest finalized
+/// block is used. The header and sync committee branch come from the standard light client
+/// bootstrap, but there is no standard endpoint proving the block roots, so their branch is
+/// built from the full beacon state served by the debug API (`/eth/v2/debug/beacon/states`).
+/// The node must have the debug API enabled, which public endpoints usually don't, and hold the
+/// state of the checkpoint slot. The state is checked against the state root of the header
+/// before it is used.
+pub async fn fetch_checkpoint(
+    beacon_url: &str,
+    slot: Option<u64>,
+    block_root: Option<FixedBytes<32>>,
+) -> Result<CheckpointUpdate, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let beacon_url = beacon_url.trim_end_matches('/');
+
+    let block_root = match (block_root, slot) {
+        (Some(block_root), _) => block_root.to_string(),
+        (None, Some(slot)) => fetch_block_root(&client, beacon_url, &slot.to_string()).await?,
+        (None, None) => fetch_block_root(&client, beacon_url, "finalized").await?,
+    };
+
+    let bootstrap: Response<Bootstrap> = client
+        .get(format!(
+            "{beacon_url}/eth/v1/beacon/light_client/bootstrap/{block_root}"
+        ))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let bootstrap = bootstrap.data;
+    let header = bootstrap.header.beacon;
+
+    let response = client
+        .get(format!(
+            "{beacon_url}/eth/v2/debug/beacon/states/{}",
+            header.slot
+        ))
+        .header(reqwest::header::ACCEPT, "application/octet-stream")
+        .send()
+        .await?
+        .error_for_status()?;
+    let fork = response
+        .headers()
+        .get("Eth-Consensus-Version")
+        .ok_or("Beacon node did not return the consensus version of the state")?
+        .to_str()?
+        .to_lowercase();
+    let state = response.bytes().await?;
+
+    let beacon_state = beacon_state_type(&fork)?;
+    let fields = beacon_state.split(&state)?;
+    let field_roots = beacon_state
+        .fields()
+        .iter()
+        .zip(fields.iter())
+        .map(|(field, data)| field.hash_tree_root(data))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (state_root, block_roots_branch) =
+        merkle_branch(&field_roots, beacon_state.fields().len(), BLOCK_ROOTS_INDEX);
+    if as_hex(&state_root) != header.state_root.to_lowercase() {
+        return Err(format!(
+            "Beacon state root {} does not match the state root {} of block {block_root}",
+            as_hex(&state_root),
+            header.state_root
+        )
+        .into());
+    }
+
+    let checkpoint = json!({
+        "header": {
+            "slot": header.slot.parse::<u64>()?,
+            "proposer_index": header.proposer_index.parse::<u64>()?,
+            "parent_root": header.parent_root,
+            "state_root": header.state_root,
+            "body_root": header.body_root,
+        },
+        "current_sync_committee": {
+            "pubkeys": bootstrap.current_sync_committee.pubkeys,
+            "aggregate_pubkey": bootstrap.current_sync_committee.aggregate_pubkey,
+        },
+        "current_sync_committee_branch": bootstrap.current_sync_committee_branch,
+        "validators_root": as_hex(fields[1]),
+        "block_roots_root": as_hex(&field_roots[BLOCK_ROOTS_INDEX]),
+        "block_roots_branch": block_roots_branch.iter().map(|node| as_hex(node)).collect::<Vec<_>>(),
+    });
+
+    Ok(serde_json::from_value(checkpoint)?)
+}
+
+/// Verify the Merkle branches of a checkpoint against the state root of its header, in the same
+/// way as the beacon light client on BridgeHub.
+pub fn verify_checkpoint(checkpoint: &CheckpointUpdate) -> Result<(), String> {
+    // The depth of the `BeaconState` tree, which is 5 before Electra and 6 from Electra onwards
+    let depth = checkpoint.current_sync_committee_branch.len();
+    if depth != 5 && depth != 6 {
+        return Err(format!(
+            "Unexpected depth {depth} of the current sync committee branch"
+        ));
+    }
+
+    let sync_committee_root = checkpoint
+        .current_sync_committee
+        .hash_tree_root()
+        .map_err(|e| format!("Failed to hash the current sync committee: {e:?}"))?;
+    if !verify_merkle_branch(
+        sync_committee_root,
+        &checkpoint.current_sync_committee_branch,
+        CURRENT_SYNC_COMMITTEE_INDEX,
+        depth,
+        checkpoint.header.state_root,
+    ) {
+        return Err("Invalid current sync committee branch".into());
+    }
+
+    if !verify_merkle_branch(
+        checkpoint.block_roots_root,
+        &checkpoint.block_roots_branch,
+        BLOCK_ROOTS_INDEX,
+        depth,
+        checkpoint.header.state_root,
+    ) {
+        return Err("Invalid block roots branch".into());
+    }
+
+    Ok(())
+}
+
+/// Root of the latest finalized beacon block
+pub async fn finalized_block_root(
+    beacon_url: &str,
+) -> Result<FixedBytes<32>, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let block_root =
+        fetch_block_root(&client, beacon_url.trim_end_matches('/'), "finalized").await?;
+    Ok(block_root.parse()?)
+}
+
+async fn fetch_block_root(
+    client: &reqwest::Client,
+    beacon_url: &str,
+    block_id: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let response: Response<HeaderResponse> = client
+        .get(format!("{beacon_url}/eth/v1/beacon/headers/{block_id}"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(response.data.root)
+}
+
+fn as_hex(data: &[u8]) -> String {
+    format!("0x{}", hex::encode(data))
+}
+
+// SSZ type descriptions, sufficient to compute the hash tree root of a `BeaconState`
+enum Ssz {
+    // Fixed-size bytes or basic value, e.g. uint64, Bytes32, BLSPubkey
+    Bytes(usize),
+    // Vector of basic values, with the size of each value
+    BasicVector(usize, usize),
+    // List of basic values, with the size of each value
+    BasicList(usize, usize),
+    Vector(Box<Ssz>, usize),
+    List(Box<Ssz>, usize),
+    Container(Vec<Ssz>),
+}
+
+use Ssz::*;
+
+impl Ssz {
+    fn fixed_size(&self) -> Option<usize> {
+        match self {
+            Bytes(size) => Some(*size),
+            BasicVector(size, length) => Some(size * length),
+            Vector(element, length) => element.fixed_size().map(|size| size * length),
+            BasicList(..) | List(..) => None,
+            Container(fields) => fields.iter().map(|field| field.fixed_size()).sum(),
+        }
+    }
+
+    fn fields(&self) -> &[Ssz] {
+        match self {
+            Container(fields) => fields,
+            _ => &[],
+        }
+    }
+
+    // Split a serialized container into its fields
+    fn split<'a>(&self, data: &'a [u8]) -> Result<Vec<&'a [u8]>, String> {
+        let fields = self.fields();
+        let mut position = 0;
+        let mut fixed = vec![];
+        let mut offsets = vec![];
+        for (index, field) in fields.iter().enumerate() {
+            let size = field.fixed_size().unwrap_or(4);
+            let part = data
+                .get(position..position + size)
+                .ok_or("SSZ container is too short")?;
+            match field.fixed_size() {
+                Some(_) => fixed.push(part),
+                None => {
+                    offsets.push((index, u32::from_le_bytes(part.try_into().unwrap()) as usize));
+                    fixed.push(&[]);
+                }
+            }
+            position += size;
+        }
+
+        let mut parts = fixed;
+        for (i, (index, offset)) in offsets.iter().enumerate() {
+            let end = offsets.get(i + 1).map_or(data.len(), |(_, next)| *next);
+            parts[*index] = data
+                .get(*offset..end)
+                .ok_or("SSZ container has an invalid offset")?;
+        }
+
+        Ok(parts)
+    }
+
+    fn hash_tree_root(&self, data: &[u8]) -> Result<[u8; 32], String> {
+        if let Some(size) = self.fixed_size() {
+            if data.len() != size {
+                return Err(format!(
+                    "Expected {size} bytes of SSZ, found {}",
+                    data.len()
+                ));
+            }
+        }
+        let root = match self {
+            Bytes(size) => merkleize(pack(data), size.div_ceil(32)),
+            BasicVector(size, length) => merkleize(pack(data), (size * length).div_ceil(32)),
+            BasicList(size, limit) => mix_in_length(
+                merkleize(pack(data), (size * limit).div_ceil(32)),
+                data.len() / size,
+            ),
+            Vector(element, length) => merkleize(element.hash_tree_roots(data)?, *length),
+            List(element, limit) => {
+                let roots = element.hash_tree_roots(data)?;
+                let length = roots.len();
+                mix_in_length(merkleize(roots, *limit), length)
+            }
+            Container(fields) => merkleize(
+                fields
+                    .iter()
+                    .zip(self.split(data)?)
+                    .map(|(field, data)| field.hash_tree_root(data))
+                    .collect::<Result<_, _>>()?,
+                fields.len(),
+            ),
+        };
+        Ok(root)
+    }
+
+    // Hash tree roots of a sequence of fixed-size elements
+    fn hash_tree_roots(&self, data: &[u8]) -> Result<Vec<[u8; 32]>, String> {
+        let size = self
+            .fixed_size()
+            .ok_or("Variable-size SSZ elements are not supported")?;
+        if !data.len().is_multiple_of(size) {
+            return Err(format!(
+                "SSZ length {} is not a multiple of {size}",
+                data.len()
+            ));
+        }
+        data.chunks(size)
+            .map(|element| self.hash_tree_root(element))
+            .collect()
+    }
+}
+
+fn pack(data: &[u8]) -> Vec<[u8; 32]> {
+    data.chunks(32)
+        .map(|chunk| {
+            let mut padded = [0u8; 32];
+            padded[..chunk.len()].copy_from_slice(chunk);
+            padded
+        })
+        .collect()
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left);
+    data[32..].copy_from_slice(right);
+    sha2_256(&data)
+}
+
+// Roots of empty subtrees, indexed by depth
+fn zero_hashes() -> &'static [[u8; 32]] {
+    static ZERO_HASHES: OnceLock<Vec<[u8; 32]>> = OnceLock::new();
+    ZERO_HASHES.get_or_init(|| {
+        let mut hashes = vec![[0u8; 32]];
+        for depth in 0..64 {
+            hashes.push(hash_pair(&hashes[depth], &hashes[depth]));
+        }
+        hashes
+    })
+}
+
+fn merkleize(mut chunks: Vec<[u8; 32]>, limit: usize) -> [u8; 32] {
+    let zero_hashes = zero_hashes();
+    let depth = limit.next_power_of_two().trailing_zeros() as usize;
+    for zero_hash in zero_hashes.iter().take(depth) {
+        if chunks.len() % 2 == 1 {
+            chunks.push(*zero_hash);
+        }
+        chunks = chunks
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+    }
+    chunks.first().copied().unwrap_or(zero_hashes[depth])
+}
+
+fn mix_in_length(root: [u8; 32], length: usize) -> [u8; 32] {
+    let mut encoded_length = [0u8; 32];
+    encoded_length[..8].copy_from_slice(&(length as u64).to_le_bytes());
+    hash_pair(&root, &encoded_length)
+}
+
+// Merkle root of the leaves and the branch proving the leaf at `index`
+fn merkle_branch(leaves: &[[u8; 32]], limit: usize, index: usize) -> ([u8; 32], Vec<[u8; 32]>) {
+    let depth = limit.next_power_of_two().trailing_zeros() as usize;
+    let mut layer = leaves.to_vec();
+    layer.resize(1 << depth, [0u8; 32]);
+    let mut index = index;
+    let mut branch = vec![];
+    for _ in 0..depth {
+        branch.push(layer[index ^ 1]);
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        index /= 2;
+    }
+    (layer[0], branch)
+}
+
+fn beacon_state_type(fork: &str) -> Result<Ssz, String> {
+    let uint64 = || Bytes(8);
+    let root = || Bytes(32);
+    let checkpoint = || Container(vec![uint64(), root()]);
+    let eth1_data = || Container(vec![root(), uint64(), root()]);
+    let validator = Container(vec![
+        Bytes(48),
+        root(),
+        uint64(),
+        Bytes(1),
+        uint64(),
+        uint64(),
+        uint64(),
+        uint64(),
+    ]);
+    let sync_committee = || {
+        Container(vec![
+            Vector(Box::new(Bytes(48)), SYNC_COMMITTEE_SIZE),
+            Bytes(48),
+        ])
+    };
+    let execution_payload_header = Container(vec![
+        root(),
+        Bytes(20),
+        root(),
+        root(),
+        Bytes(256),
+        root(),
+        uint64(),
+        uint64(),
+        uint64(),
+        uint64(),
+        BasicList(1, 32),
+        Bytes(32),
+        root(),
+        root(),
+        root(),
+        uint64(),
+        uint64(),
+    ]);
+
+    let mut fields = vec![
+        // genesis_time, genesis_validators_root, slot, fork
+        uint64(),
+        root(),
+        uint64(),
+        Container(vec![Bytes(4), Bytes(4), uint64()]),
+        // latest_block_header
+        Container(vec![uint64(), uint64(), root(), root(), root()]),
+        // block_roots, state_roots, historical_roots
+        Vector(Box::new(root()), SLOTS_PER_HISTORICAL_ROOT),
+        Vector(Box::new(root()), SLOTS_PER_HISTORICAL_ROOT),
+        List(Box::new(root()), 1 << 24),
+        // eth1_data, eth1_data_votes, eth1_deposit_index
+        eth1_data(),
+        List(Box::new(eth1_data()), 2048),
+        uint64(),
+        // validators, balances
+        List(Box::new(validator), 1 << 40),
+        BasicList(8, 1 << 40),
+        // randao_mixes, slashings
+        Vector(Box::new(root()), EPOCHS_PER_HISTORICAL_VECTOR),
+        BasicVector(8, EPOCHS_PER_SLASHINGS_VECTOR),
+        // previous_epoch_participation, current_epoch_participation
+        BasicList(1, 1 << 40),
+        BasicList(1, 1 << 40),
+        // justification_bits, previous_justified_checkpoint, current_justified_checkpoint,
+        // finalized_checkpoint
+        Bytes(1),
+        checkpoint(),
+        checkpoint(),
+        checkpoint(),
+        // inactivity_scores
+        BasicList(8, 1 << 40),
+        // current_sync_committee, next_sync_committee
+        sync_committee(),
+        sync_committee(),
+        // latest_execution_payload_header
+        execution_payload_header,
+        // next_withdrawal_index, next_withdrawal_validator_index, historical_summaries
+        uint64(),
+        uint64(),
+        List(Box::new(Container(vec![root(), root()])), 1 << 24),
+    ];
+
+    match fork {
+        "deneb" => {}
+        "electra" | "fulu" => {
+            fields.extend([
+                // deposit_requests_start_index, deposit_balance_to_consume,
+                // exit_balance_to_consume, earliest_exit_epoch,
+                // consolidation_balance_to_consume, earliest_consolidation_epoch
+                uint64(),
+                uint64(),
+                uint64(),
+                uint64(),
+                uint64(),
+                uint64(),
+                // pending_deposits
+                List(
+                    Box::new(Container(vec![
+                        Bytes(48),
+                        root(),
+                        uint64(),
+                        Bytes(96),
+                        uint64(),
+                    ])),
+                    1 << 27,
+                ),
+                // pending_partial_withdrawals
+                List(
+                    Box::new(Container(vec![uint64(), uint64(), uint64()])),
+                    1 << 27,
+                ),
+                // pending_consolidations
+                List(Box::new(Container(vec![uint64(), uint64()])), 1 << 18),
+            ]);
+            if fork == "fulu" {
+                // proposer_lookahead
+                fields.push(BasicVector(8, 64));
+            }
+        }
+        _ => return Err(format!("Unsupported beacon state fork: {fork}")),
+    }
+
+    Ok(Container(fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{self, Response};
+    use std::io::Read;
+
+    const FORKS: [&str; 3] = ["deneb", "electra", "fulu"];
+
+    // Responses of a beacon node for a synthetic state of each fork. The state was serialized,
+    // and the expected roots and branches computed, with the SSZ crates of Lighthouse
+    // (`ethereum_ssz`, `ssz_types` and `tree_hash`).
+    fn fixture(fork: &str, name: &str) -> Vec<u8> {
+        let path = format!(
+            "{}/fixtures/beacon/{fork}/{name}",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        std::fs::read(&path).unwrap_or_else(|e| panic!("Failed to read {path}: {e}"))
+    }
+
+    fn json_fixture(fork: &str, name: &str) -> serde_json::Value {
+        serde_json::from_slice(&fixture(fork, name)).expect("fixture is JSON")
+    }
+
+    fn state(fork: &str) -> Vec<u8> {
+        let mut state = vec![];
+        flate2::read::GzDecoder::new(&fixture(fork, "state.ssz.gz")[..])
+            .read_to_end(&mut state)
+            .expect("fixture is gzipped");
+        state
+    }
+
+    fn expected(fork: &str, name: &str) -> String {
+        json_fixture(fork, "expected.json")[name]
+            .as_str()
+            .expect("expected value is a string")
+            .to_owned()
+    }
+
+    // Serve the recorded responses for a block of the given fork, answering only the requests
+    // made for it
+    async fn beacon_node(fork: &'static str, state: Vec<u8>) -> String {
+        let (header, bootstrap) = (
+            json_fixture(fork, "header.json"),
+            json_fixture(fork, "bootstrap.json"),
+        );
+        let block_root = expected(fork, "block_root");
+        let slot = header["data"]["header"]["message"]["slot"]
+            .as_str()
+            .expect("slot is a string")
+            .to_owned();
+        stub::serve(move |request| match request.path.as_str() {
+            _ if request.method != "GET" => Response::not_found(),
+            "/eth/v1/beacon/headers/finalized" => Response::json(&header),
+            path if path == format!("/eth/v1/beacon/headers/{slot}") => Response::json(&header),
+            path if path == format!("/eth/v1/beacon/light_client/bootstrap/{block_root}") => {
+                Response::json(&bootstrap)
+            }
+            path if path == format!("/eth/v2/debug/beacon/states/{slot}") => {
+                Response::ok(state.clone()).header("Eth-Consensus-Version", fork)
+            }
+            _ => Response::not_found(),
+        })
+        .await
+    }
+
+    #[test]
+    fn zero_hashes_match_the_ssz_spec() {
+        assert_eq!(zero_hashes()[0], [0u8; 32]);
+        assert_eq!(
+            as_hex(&zero_hashes()[1]),
+            "0xf5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"
+        );
+        assert_eq!(
+            as_hex(&zero_hashes()[2]),
+            "0xdb56114e00fdd4c1f85c892bf35ac9a89289aaecb1ebd0a96cde606a748b5d71"
+        );
+    }
+
+    #[test]
+    fn hashes_beacon_states() {
+        for fork in FORKS {
+            let root = beacon_state_type(fork)
+                .and_then(|state_type| state_type.hash_tree_root(&state(fork)))
+                .unwrap_or_else(|e| panic!("Failed to hash the {fork} state: {e}"));
+            assert_eq!(as_hex(&root), expected(fork, "state_root"), "{fork}");
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_checkpoints_from_beacon_node() {
+        for fork in FORKS {
+            let beacon_url = beacon_node(fork, state(fork)).await;
+            let slot = json_fixture(fork, "header.json")["data"]["header"]["message"]["slot"]
+                .as_str()
+                .and_then(|slot| slot.parse().ok());
+            let block_root = expected(fork, "block_root").parse().ok();
+
+            for (slot, block_root) in [(None, None), (slot, None), (None, block_root)] {
+                let checkpoint = fetch_checkpoint(&beacon_url, slot, block_root)
+                    .await
+                    .unwrap_or_else(|e| panic!("Failed to build the {fork} checkpoint: {e}"));
+                verify_checkpoint(&checkpoint)
+                    .unwrap_or_else(|e| panic!("Invalid {fork} checkpoint: {e}"));
+
+                let block_roots_branch: Vec<String> = checkpoint
+                    .block_roots_branch
+                    .iter()
+                    .map(|node| as_hex(node.as_bytes()))
+                    .collect();
+                let expected_branch: Vec<String> = serde_json::from_value(
+                    json_fixture(fork, "expected.json")["block_roots_branch"].clone(),
+                )
+                .expect("branch is a list of strings");
+                assert_eq!(block_roots_branch, expected_branch, "{fork}");
+                assert_eq!(
+                    as_hex(checkpoint.block_roots_root.as_bytes()),
+                    expected(fork, "block_roots_root"),
+                    "{fork}"
+                );
+                assert_eq!(
+                    as_hex(checkpoint.validators_root.as_bytes()),
+                    expected(fork, "validators_root"),
+                    "{fork}"
+                );
+                assert_eq!(
+                    as_hex(
+                        checkpoint
+                            .header
+                            .hash_tree_root()
+                            .expect("header hashes")
+                            .as_bytes()
+                    ),
+                    expected(fork, "block_root"),
+                    "{fork}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_state_not_matching_header() {
+        let mut state = state("electra");
+        // A byte of the block roots
+        state[200_000] ^= 1;
+        let beacon_url = beacon_node("electra", state).await;
+
+        let error = fetch_checkpoint(&beacon_url, None, None)
+            .await
+            .expect_err("state does not match the header");
+        assert!(
+            error.to_string().contains("does not match the state root"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_finalized_block_root() {
+        let beacon_url = beacon_node("electra", vec![]).await;
+        let block_root = finalized_block_root(&format!("{beacon_url}/"))
+            .await
+            .expect("block root is fetched");
+        assert_eq!(block_root.to_string(), expected("electra", "block_root"));
+    }
+
+    #[tokio::test]
+    async fn rejects_tampered_branches() {
+        let beacon_url = beacon_node("deneb", state("deneb")).await;
+        let checkpoint = fetch_checkpoint(&beacon_url, None, None)
+            .await
+            .expect("checkpoint is built");
+
+        let mut tampered = checkpoint.clone();
+        tampered.block_roots_branch[2].0[0] ^= 1;
+        assert_eq!(
+            verify_checkpoint(&tampered),
+            Err("Invalid block roots branch".into())
+        );
+
+        let mut tampered = checkpoint;
+        tampered.current_sync_committee.pubkeys[7].0[0] ^= 1;
+        assert_eq!(
+            verify_checkpoint(&tampered),
+            Err("Invalid current sync committee branch".into())
+        );
+    }
+}
@@ -1,304 +1,21 @@
-use crate::helpers::calculate_delivery_fee;
-use crate::{
-    constants::*, Context, ForceCheckpointArgs, GatewayAddressArgs, GatewayOperatingModeEnum,
-    OperatingModeEnum, PricingParametersArgs, RegisterEtherArgs, UpdateAssetArgs, UpgradeArgs,
-};
-use alloy_primitives::{utils::format_units, U256};
-use codec::Encode;
-use snowbridge_router_primitives::inbound::GlobalConsensusEthereumConvertsFor;
-use sp_arithmetic::FixedU128;
-use sp_crypto_hashing::twox_128;
-use std::{fs::File, io::Read};
-use subxt::utils::MultiAddress;
-use subxt::utils::Static;
-
-type CheckpointUpdate = snowbridge_beacon_primitives::CheckpointUpdate<512>;
-
-use crate::asset_hub_runtime::runtime_types::pallet_assets;
-use crate::asset_hub_runtime::RuntimeCall as AssetHubRuntimeCall;
-
-use crate::bridge_hub_runtime::runtime_types::{
-    snowbridge_core::{
-        operating_mode::BasicOperatingMode,
-        outbound::v1::{Initializer, OperatingMode},
-        pricing::{PricingParameters, Rewards},
-    },
-    snowbridge_pallet_ethereum_client, snowbridge_pallet_inbound_queue,
-    snowbridge_pallet_outbound_queue, snowbridge_pallet_system,
-};
-use crate::bridge_hub_runtime::RuntimeCall as BridgeHubRuntimeCall;
-
-#[cfg(feature = "polkadot")]
-pub mod asset_hub_polkadot_types {
-    pub use crate::asset_hub_runtime::runtime_types::staging_xcm::v4::{
-        junction::Junction::AccountKey20,
-        junction::Junction::GlobalConsensus,
-        junction::NetworkId,
-        junctions::Junctions::{X1, X2},
-        location::Location,
-    };
-
-    pub fn get_ether_id(chain_id: u64) -> Location {
-        return Location {
-            parents: 2,
-            interior: X1([GlobalConsensus(NetworkId::Ethereum { chain_id })]),
-        };
-    }
-    pub fn get_asset_id(chain_id: u64, key: [u8; 20]) -> Location {
-        return Location {
-            parents: 2,
-            interior: X2([
-                GlobalConsensus(NetworkId::Ethereum { chain_id }),
-                AccountKey20 { network: None, key },
-            ]),
-        };
-    }
-}
-
-#[cfg(feature = "paseo")]
-pub mod asset_hub_paseo_types {
-    pub use crate::asset_hub_runtime::runtime_types::staging_xcm::v3::multilocation::MultiLocation as Location;
-    pub use crate::asset_hub_runtime::runtime_types::xcm::v3::{
-        junction::Junction::AccountKey20,
-        junction::Junction::GlobalConsensus,
-        junction::NetworkId,
-        junctions::Junctions::{X1, X2},
-    };
-
-    pub fn get_ether_id(chain_id: u64) -> Location {
-        return Location {
-            parents: 2,
-            interior: X1(GlobalConsensus(NetworkId::Ethereum { chain_id })),
-        };
-    }
-    pub fn get_asset_id(chain_id: u64, key: [u8; 20]) -> Location {
-        return Location {
-            parents: 2,
-            interior: X2(
-                GlobalConsensus(NetworkId::Ethereum { chain_id }),
-                AccountKey20 { network: None, key },
-            ),
-        };
-    }
-}
-
-#[cfg(feature = "westend")]
-pub mod asset_hub_westend_types {
-    pub use crate::asset_hub_runtime::runtime_types::staging_xcm::v5::{
-        junction::Junction::AccountKey20,
-        junction::Junction::GlobalConsensus,
-        junction::NetworkId,
-        junctions::Junctions::{X1, X2},
-        location::Location,
-    };
-    pub fn get_ether_id(chain_id: u64) -> Location {
-        return Location {
-            parents: 2,
-            interior: X1([GlobalConsensus(NetworkId::Ethereum { chain_id })]),
-        };
-    }
-    pub fn get_asset_id(chain_id: u64, key: [u8; 20]) -> Location {
-        return Location {
-            parents: 2,
-            interior: X2([
-                GlobalConsensus(NetworkId::Ethereum { chain_id }),
-                AccountKey20 { network: None, key },
-            ]),
-        };
-    }
-}
-
-pub fn gateway_operating_mode(operating_mode: &GatewayOperatingModeEnum) -> BridgeHubRuntimeCall {
-    let mode = match operating_mode {
-        GatewayOperatingModeEnum::Normal => OperatingMode::Normal,
-        GatewayOperatingModeEnum::RejectingOutboundMessages => {
-            OperatingMode::RejectingOutboundMessages
-        }
-    };
-    BridgeHubRuntimeCall::EthereumSystem(
-        snowbridge_pallet_system::pallet::Call::set_operating_mode { mode },
-    )
-}
-
-pub fn inbound_queue_operating_mode(param: &OperatingModeEnum) -> BridgeHubRuntimeCall {
-    let mode = match param {
-        OperatingModeEnum::Normal => BasicOperatingMode::Normal,
-        OperatingModeEnum::Halted => BasicOperatingMode::Halted,
-    };
-    BridgeHubRuntimeCall::EthereumInboundQueue(
-        snowbridge_pallet_inbound_queue::pallet::Call::set_operating_mode { mode },
-    )
-}
-
-pub fn ethereum_client_operating_mode(param: &OperatingModeEnum) -> BridgeHubRuntimeCall {
-    let mode = match param {
-        OperatingModeEnum::Normal => BasicOperatingMode::Normal,
-        OperatingModeEnum::Halted => BasicOperatingMode::Halted,
-    };
-    BridgeHubRuntimeCall::EthereumBeaconClient(
-        snowbridge_pallet_ethereum_client::pallet::Call::set_operating_mode { mode },
-    )
-}
-
-pub fn outbound_queue_operating_mode(param: &OperatingModeEnum) -> BridgeHubRuntimeCall {
-    let mode = match param {
-        OperatingModeEnum::Normal => BasicOperatingMode::Normal,
-        OperatingModeEnum::Halted => BasicOperatingMode::Halted,
-    };
-    BridgeHubRuntimeCall::EthereumOutboundQueue(
-        snowbridge_pallet_outbound_queue::pallet::Call::set_operating_mode { mode },
-    )
-}
-
-pub fn upgrade(params: &UpgradeArgs) -> BridgeHubRuntimeCall {
-    BridgeHubRuntimeCall::EthereumSystem(snowbridge_pallet_system::pallet::Call::upgrade {
-        impl_address: params.logic_address.into_array().into(),
-        impl_code_hash: params.logic_code_hash.0.into(),
-        initializer: Some(Initializer {
-            params: params.initializer_params.clone().into(),
-            maximum_required_gas: params.initializer_gas,
-        }),
-    })
-}
-
-pub async fn pricing_parameters(
-    context: &Context,
-    params: &PricingParametersArgs,
-) -> Result<(BridgeHubRuntimeCall, AssetHubRuntimeCall), Box<dyn std::error::Error>> {
-    // BridgeHub parameters
-    let pricing_params: PricingParameters<u128> = PricingParameters {
-        exchange_rate: Static(FixedU128::from_rational(
-            params.exchange_rate_numerator.into(),
-            params.exchange_rate_denominator.into(),
-        )),
-        multiplier: Static(FixedU128::from_rational(
-            params.multiplier_numerator.into(),
-            params.multiplier_denominator.into(),
-        )),
-        fee_per_gas: crate::bridge_hub_runtime::runtime_types::primitive_types::U256(
-            params.fee_per_gas.into_limbs(),
-        ),
-        rewards: Rewards {
-            local: params.local_reward.to::<u128>(),
-            remote: crate::bridge_hub_runtime::runtime_types::primitive_types::U256(
-                params.remote_reward.into_limbs(),
-            ),
-        },
-    };
-
-    let outbound_delivery_fee =
-        calculate_delivery_fee(&context.bridge_hub_api, &pricing_params).await?;
-
-    let total_outbound_fee = outbound_delivery_fee.local + outbound_delivery_fee.remote;
-
-    // Adjust outbound fee up by 10% as a buffer
-    let total_outbound_fee_adjusted = total_outbound_fee.saturating_add(total_outbound_fee / 10);
-
-    eprintln!("BridgeHub:");
-    eprintln!(
-        "  ExchangeRate: {} ETH/{}",
-        params.exchange_rate_numerator as f64 / params.exchange_rate_denominator as f64,
-        POLKADOT_SYMBOL
-    );
-    eprintln!(
-        "  FeePerGas: {} GWEI",
-        format_units(params.fee_per_gas, "gwei").unwrap(),
-    );
-    eprintln!(
-        "  LocalReward: {} {} [{} PLANCK]",
-        format_units(U256::from(params.local_reward), POLKADOT_DECIMALS).unwrap(),
-        POLKADOT_SYMBOL,
-        params.local_reward,
-    );
-    eprintln!(
-        "  RemoteReward: {} ETH [{} WEI]",
-        format_units(params.remote_reward, "eth").unwrap(),
-        params.remote_reward
-    );
-    eprintln!("AssetHub:");
-    eprintln!(
-        "  BaseFee: {} {}, [{} PLANCK]",
-        format_units(U256::from(total_outbound_fee_adjusted), POLKADOT_DECIMALS).unwrap(),
-        POLKADOT_SYMBOL,
-        total_outbound_fee_adjusted
-    );
-
-    // AssetHub parameters
-    let asset_hub_outbound_fee_storage_key: Vec<u8> =
-        twox_128(b":BridgeHubEthereumBaseFee:").to_vec();
-    let asset_hub_outbound_fee_encoded: Vec<u8> = total_outbound_fee_adjusted.encode();
-
-    eprintln!(
-        "Storage key for 'BridgeHubEthereumBaseFee': 0x{}",
-        hex::encode(&asset_hub_outbound_fee_storage_key)
-    );
-
-    Ok((
-        BridgeHubRuntimeCall::EthereumSystem(
-            snowbridge_pallet_system::pallet::Call::set_pricing_parameters {
-                params: pricing_params,
-            },
-        ),
-        AssetHubRuntimeCall::System(
-            crate::asset_hub_runtime::runtime_types::frame_system::pallet::Call::set_storage {
-                items: vec![(
-                    asset_hub_outbound_fee_storage_key,
-                    asset_hub_outbound_fee_encoded,
-                )],
-            },
-        ),
-    ))
-}
-
-pub fn set_assethub_fee(fee: u128) -> AssetHubRuntimeCall {
-    let asset_hub_outbound_fee_storage_key: Vec<u8> =
-        twox_128(b":BridgeHubEthereumBaseFee:").to_vec();
-    let asset_hub_outbound_fee_encoded: Vec<u8> = fee.encode();
-
-    AssetHubRuntimeCall::System(
-        crate::asset_hub_runtime::runtime_types::frame_system::pallet::Call::set_storage {
-            items: vec![(
-                asset_hub_outbound_fee_storage_key,
-                asset_hub_outbound_fee_encoded,
-            )],
-        },
-    )
-}
-
-pub fn force_checkpoint(params: &ForceCheckpointArgs) -> BridgeHubRuntimeCall {
-    let mut file = File::open(params.checkpoint.clone()).expect("File not found");
-    let mut data = String::new();
-    file.read_to_string(&mut data)
-        .expect("Failed to read the file");
-    let checkpoint: CheckpointUpdate = serde_json::from_str(&data).unwrap();
-    BridgeHubRuntimeCall::EthereumBeaconClient(
-        snowbridge_pallet_ethereum_client::pallet::Call::force_checkpoint {
-            update: Box::new(Static(checkpoint)),
-        },
-    )
-}
-
-pub fn set_gateway_address(params: &GatewayAddressArgs) -> BridgeHubRuntimeCall {
-    let storage_key = sp_crypto_hashing::twox_128(b":EthereumGatewayAddress:").to_vec();
-    let storage_value = params.gateway_address.into_array().encode();
-    BridgeHubRuntimeCall::System(
-        crate::bridge_hub_runtime::runtime_types::frame_system::pallet::Call::set_storage {
-            items: vec![(storage_key, storage_value)],
-        },
-    )
-}
+use super::asset_hub_runtime::runtime_types::pallet_assets;
+use super::asset_hub_runtime::RuntimeCall as AssetHubRuntimeCall;
+use super::asset_hub_types::*;
+use super::assets::{ensure_unique, ensure_unregistered};
+use super::bridge_hub_runtime::RuntimeCall as BridgeHubRuntimeCall;
+use super::NETWORK;
+use crate::provenance;
+use crate::{manifest, Context, RegisterErc20Args, RegisterEtherArgs, UpdateAssetArgs};
+use alloy_primitives::Address;
+use serde::Deserialize;
+use sp_crypto_hashing::blake2_256;
+use std::path::Path;
+use subxt::utils::{AccountId32, MultiAddress};
 
 pub fn make_asset_sufficient(params: &UpdateAssetArgs) -> AssetHubRuntimeCall {
-    use subxt::utils::AccountId32;
-    let chain_id = crate::bridge_hub_runtime::CHAIN_ID;
-    #[cfg(feature = "paseo")]
-    use asset_hub_paseo_types::*;
-    #[cfg(feature = "polkadot")]
-    use asset_hub_polkadot_types::*;
-    #[cfg(feature = "westend")]
-    use asset_hub_westend_types::*;
-    let asset_id = get_asset_id(chain_id, params.contract_id.into_array().into());
-    let owner = GlobalConsensusEthereumConvertsFor::<[u8; 32]>::from_chain_id(&chain_id);
+    let chain_id = NETWORK.chain_id();
+    let asset_id = get_asset_id(chain_id, params.contract_id.into_array());
+    let owner = NETWORK.asset_owner();
     AssetHubRuntimeCall::ForeignAssets(pallet_assets::pallet::Call2::force_asset_status {
         id: asset_id,
         owner: MultiAddress::<AccountId32, ()>::Id(owner.into()),
@@ -312,14 +29,8 @@ pub fn make_asset_sufficient(params: &UpdateAssetArgs) -> AssetHubRuntimeCall {
 }
 
 pub fn force_set_metadata(params: &UpdateAssetArgs) -> AssetHubRuntimeCall {
-    let chain_id = crate::bridge_hub_runtime::CHAIN_ID;
-    #[cfg(feature = "paseo")]
-    use asset_hub_paseo_types::*;
-    #[cfg(feature = "polkadot")]
-    use asset_hub_polkadot_types::*;
-    #[cfg(feature = "westend")]
-    use asset_hub_westend_types::*;
-    let asset_id = get_asset_id(chain_id, params.contract_id.into_array().into());
+    let chain_id = NETWORK.chain_id();
+    let asset_id = get_asset_id(chain_id, params.contract_id.into_array());
     AssetHubRuntimeCall::ForeignAssets(pallet_assets::pallet::Call2::force_set_metadata {
         id: asset_id,
         name: params.name.as_bytes().to_vec(),
@@ -329,18 +40,17 @@ pub fn force_set_metadata(params: &UpdateAssetArgs) -> AssetHubRuntimeCall {
     })
 }
 
-pub fn register_ether(params: &RegisterEtherArgs) -> (AssetHubRuntimeCall, AssetHubRuntimeCall) {
-    use subxt::utils::AccountId32;
-    let chain_id = crate::bridge_hub_runtime::CHAIN_ID;
-    #[cfg(feature = "paseo")]
-    use asset_hub_paseo_types::*;
-    #[cfg(feature = "polkadot")]
-    use asset_hub_polkadot_types::*;
-    #[cfg(feature = "westend")]
-    use asset_hub_westend_types::*;
+pub async fn register_ether(
+    context: &Context,
+    params: &RegisterEtherArgs,
+) -> Result<(AssetHubRuntimeCall, AssetHubRuntimeCall), Box<dyn std::error::Error>> {
+    let chain_id = NETWORK.chain_id();
 
     let asset_id = get_ether_id(chain_id);
-    let owner = GlobalConsensusEthereumConvertsFor::<[u8; 32]>::from_chain_id(&chain_id);
+    ensure_unregistered(context, &asset_id)
+        .await
+        .map_err(|e| format!("Invalid Ether asset: {e}"))?;
+    let owner = NETWORK.asset_owner();
 
     let force_register =
         AssetHubRuntimeCall::ForeignAssets(pallet_assets::pallet::Call2::force_create {
@@ -358,312 +68,268 @@ pub fn register_ether(params: &RegisterEtherArgs) -> (AssetHubRuntimeCall, Asset
             is_frozen: false,
         });
 
-    return (force_register, metadata);
+    Ok((force_register, metadata))
 }
 
-#[cfg(feature = "polkadot")]
-fn register_polkadot_native_asset(
-    location: crate::bridge_hub_runtime::runtime_types::xcm::VersionedLocation,
-    name: &'static str,
-    symbol: &'static str,
-    decimals: u8,
-) -> BridgeHubRuntimeCall {
-    use crate::bridge_hub_runtime::runtime_types::{bounded_collections, snowbridge_core};
-
-    let call = BridgeHubRuntimeCall::EthereumSystem(
-        snowbridge_pallet_system::pallet::Call::register_token {
-            location: location.into(),
-            metadata: snowbridge_core::AssetMetadata {
-                name: bounded_collections::bounded_vec::BoundedVec(name.as_bytes().to_vec()),
-                symbol: bounded_collections::bounded_vec::BoundedVec(symbol.as_bytes().to_vec()),
-                decimals,
-            },
+#[derive(Debug, Deserialize)]
+struct Erc20Assets {
+    assets: Vec<Erc20Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Erc20Asset {
+    pub contract: String,
+    pub min_balance: u128,
+    #[serde(default)]
+    pub is_sufficient: bool,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+pub fn load_erc20_assets(
+    params: &RegisterErc20Args,
+) -> Result<Vec<Erc20Asset>, Box<dyn std::error::Error>> {
+    let manifest: Erc20Assets = manifest::load_data(&params.assets)?;
+    Ok(manifest.assets)
+}
+
+pub async fn register_erc20_assets(
+    context: &Context,
+    params: &RegisterErc20Args,
+) -> Result<Vec<AssetHubRuntimeCall>, Box<dyn std::error::Error>> {
+    let assets = load_erc20_assets(params)?;
+    let ids = assets
+        .iter()
+        .enumerate()
+        .map(|(index, asset)| {
+            erc20_asset_id(asset)
+                .map(|(contract, _)| contract)
+                .map_err(|e| format!("Invalid asset #{index} ({}): {e}", asset.contract))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    ensure_unique(&ids)?;
+
+    eprintln!("ERC20 tokens:");
+    let mut calls = vec![];
+    for (index, asset) in assets.iter().enumerate() {
+        let invalid = |e: String| format!("Invalid asset #{index} ({}): {e}", asset.contract);
+        let (_, asset_id) = erc20_asset_id(asset).map_err(invalid)?;
+        ensure_unregistered(context, &asset_id)
+            .await
+            .map_err(|e| invalid(e.to_string()))?;
+        calls.extend(register_erc20_asset(asset).map_err(invalid)?);
+    }
+
+    Ok(calls)
+}
+
+fn erc20_asset_id(asset: &Erc20Asset) -> Result<(Address, Location), String> {
+    let contract: Address = asset
+        .contract
+        .parse()
+        .map_err(|_| "invalid contract address".to_owned())?;
+    Ok((
+        contract,
+        get_asset_id(NETWORK.chain_id(), contract.into_array()),
+    ))
+}
+
+// Create the asset, setting its metadata if given
+pub fn register_erc20_asset(asset: &Erc20Asset) -> Result<Vec<AssetHubRuntimeCall>, String> {
+    let (contract, asset_id) = erc20_asset_id(asset)?;
+    let owner = NETWORK.asset_owner();
+
+    let mut calls = vec![AssetHubRuntimeCall::ForeignAssets(
+        pallet_assets::pallet::Call2::force_create {
+            id: asset_id.clone(),
+            min_balance: asset.min_balance,
+            is_sufficient: asset.is_sufficient,
+            owner: MultiAddress::<AccountId32, ()>::Id(owner.into()),
         },
+    )];
+
+    match (&asset.name, &asset.symbol, asset.decimals) {
+        (Some(name), Some(symbol), Some(decimals)) => {
+            eprintln!(
+                "  {}: {}, {} decimals, {}",
+                symbol, name, decimals, contract
+            );
+            calls.push(AssetHubRuntimeCall::ForeignAssets(
+                pallet_assets::pallet::Call2::force_set_metadata {
+                    id: asset_id,
+                    name: name.as_bytes().to_vec(),
+                    symbol: symbol.as_bytes().to_vec(),
+                    decimals,
+                    is_frozen: false,
+                },
+            ));
+        }
+        (None, None, None) => eprintln!("  {}", contract),
+        _ => return Err("name, symbol and decimals must be given together".into()),
+    }
+
+    Ok(calls)
+}
+
+pub fn runtime_code_hash(path: &Path) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let code =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let code_hash = blake2_256(&code);
+
+    eprintln!("Runtime:");
+    eprintln!("  Code Size: {}", code.len());
+    eprintln!("  Code Hash: 0x{}", hex::encode(code_hash));
+    provenance::record("Runtime", "CodeSize", code.len());
+    provenance::record(
+        "Runtime",
+        "CodeHash",
+        format!("0x{}", hex::encode(code_hash)),
     );
+    eprintln!(
+        "  After enactment, apply the upgrade with System.apply_authorized_upgrade using the same file"
+    );
+
+    Ok(code_hash)
+}
 
-    return call;
+pub fn bridge_hub_authorize_upgrade(code_hash: [u8; 32]) -> BridgeHubRuntimeCall {
+    BridgeHubRuntimeCall::System(
+        super::bridge_hub_runtime::runtime_types::frame_system::pallet::Call::authorize_upgrade {
+            code_hash: code_hash.into(),
+        },
+    )
 }
 
-#[cfg(feature = "polkadot")]
-pub fn token_registrations() -> Vec<BridgeHubRuntimeCall> {
-    use crate::bridge_hub_runtime::runtime_types::{
-        staging_xcm::v4::{
-            junction::Junction::*, junction::NetworkId::*, junctions::Junctions::*,
-            location::Location,
+pub fn asset_hub_authorize_upgrade(code_hash: [u8; 32]) -> AssetHubRuntimeCall {
+    AssetHubRuntimeCall::System(
+        super::asset_hub_runtime::runtime_types::frame_system::pallet::Call::authorize_upgrade {
+            code_hash: code_hash.into(),
         },
-        xcm::VersionedLocation,
-    };
-    use hex_literal::hex;
-    return vec![
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: Here,
-            }),
-            "Polkadot",
-            "DOT",
-            10u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 2,
-                interior: X1([GlobalConsensus(Kusama)]),
-            }),
-            "Kusama",
-            "KSM",
-            12u8,
-        ),
-        /*
-         * Parachains
-         */
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([Parachain(2004), PalletInstance(10)]),
-            }),
-            "Glimmer",
-            "GLMR",
-            18u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([
-                    Parachain(2030),
-                    GeneralKey {
-                        length: 2,
-                        data: hex!(
-                            "0001000000000000000000000000000000000000000000000000000000000000"
-                        ),
-                    },
-                ]),
-            }),
-            "Bifrost Native Token",
-            "BNC",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([
-                    Parachain(2030),
-                    GeneralKey {
-                        length: 2,
-                        data: hex!(
-                            "0900000000000000000000000000000000000000000000000000000000000000"
-                        ),
-                    },
-                ]),
-            }),
-            "Voucher DOT",
-            "vDOT",
-            10u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([Parachain(2034), GeneralIndex(0)]),
-            }),
-            "Hydration",
-            "HDX",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2039)]),
-            }),
-            "Integritee TEER",
-            "TEER",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2051)]),
-            }),
-            "Ajuna Polkadot AJUN",
-            "AJUN",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(3344)]),
-            }),
-            "Polimec",
-            "PLMC",
-            10u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(3370)]),
-            }),
-            "LAOS",
-            "LAOS",
-            18u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2086)]),
-            }),
-            "KILT Spiritnet",
-            "KILT",
-            15u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2006)]),
-            }),
-            "Astar",
-            "ASTR",
-            18u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([
-                    Parachain(2031),
-                    GeneralKey {
-                        length: 2,
-                        data: hex!(
-                            "0001000000000000000000000000000000000000000000000000000000000000"
-                        ),
-                    },
-                ]),
-            }),
-            "Centrifuge",
-            "CFG",
-            18u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2101)]),
-            }),
-            "Subsocial",
-            "SUB",
-            10u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2035)]),
-            }),
-            "Phala Token",
-            "PHA",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([
-                    Parachain(2012),
-                    GeneralKey {
-                        length: 4,
-                        data: hex!(
-                            "5041524100000000000000000000000000000000000000000000000000000000"
-                        ),
-                    },
-                ]),
-            }),
-            "Parallel",
-            "PARA",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2008)]),
-            }),
-            "Crust Parachain Native Token",
-            "CRU",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X1([Parachain(2104)]),
-            }),
-            "Manta",
-            "MANTA",
-            18u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([
-                    Parachain(2000),
-                    GeneralKey {
-                        length: 2,
-                        data: hex!(
-                            "0000000000000000000000000000000000000000000000000000000000000000"
-                        ),
-                    },
-                ]),
-            }),
-            "Acala",
-            "ACA",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X2([
-                    Parachain(2000),
-                    GeneralKey {
-                        length: 2,
-                        data: hex!(
-                            "0003000000000000000000000000000000000000000000000000000000000000"
-                        ),
-                    },
-                ]),
-            }),
-            "Liquid DOT",
-            "LDOT",
-            10u8,
-        ),
-        /*
-         * Meme coins
-         */
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X3([Parachain(1000), PalletInstance(50), GeneralIndex(30)]),
-            }),
-            "DED",
-            "DED",
-            10u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X3([Parachain(1000), PalletInstance(50), GeneralIndex(23)]),
-            }),
-            "PINK",
-            "PINK",
-            10u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X3([Parachain(1000), PalletInstance(50), GeneralIndex(86)]),
-            }),
-            "Kolkadot",
-            "KOL",
-            12u8,
-        ),
-        register_polkadot_native_asset(
-            VersionedLocation::V4(Location {
-                parents: 1,
-                interior: X3([Parachain(1000), PalletInstance(50), GeneralIndex(31337)]),
-            }),
-            "GAVUN WUD",
-            "WUD",
-            10u8,
-        ),
-    ];
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChainEnum;
+    use codec::{Decode, Encode};
+    use subxt::ext::scale_value::{scale::decode_as_type, ValueDef};
+
+    fn update_asset_args() -> UpdateAssetArgs {
+        UpdateAssetArgs {
+            contract_id: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+                .parse()
+                .unwrap(),
+            name: "Wrapped Ether".into(),
+            symbol: "WETH".into(),
+            decimals: 18,
+            min_balance: 1,
+            is_sufficient: true,
+            is_frozen: false,
+        }
+    }
+
+    // Decode the call with the bundled metadata of the chain, returning its pallet and name
+    fn decode(chain: ChainEnum, call: &[u8]) -> (String, String) {
+        let decoder = crate::decoder(NETWORK).unwrap();
+        let metadata = decoder.metadata(chain);
+        let mut input = call;
+        let value = decode_as_type(
+            &mut input,
+            metadata.outer_enums().call_enum_ty(),
+            metadata.types(),
+        )
+        .unwrap();
+        assert!(input.is_empty(), "{NETWORK} {chain}: bytes left over");
+
+        let ValueDef::Variant(pallet) = value.value else {
+            panic!("{NETWORK} {chain}: call is not a variant");
+        };
+        let name = match &pallet.values.values().next().map(|value| &value.value) {
+            Some(ValueDef::Variant(call)) => call.name.clone(),
+            _ => panic!("{NETWORK} {chain}: pallet call is not a variant"),
+        };
+        (pallet.name, name)
+    }
+
+    fn assert_asset_hub_call(call: &AssetHubRuntimeCall, pallet: &str, name: &str) {
+        let encoded = call.encode();
+        let decoded = AssetHubRuntimeCall::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(
+            decode(ChainEnum::AssetHub, &encoded),
+            (pallet.into(), name.into())
+        );
+    }
+
+    fn assert_bridge_hub_call(call: &BridgeHubRuntimeCall, pallet: &str, name: &str) {
+        let encoded = call.encode();
+        let decoded = BridgeHubRuntimeCall::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(
+            decode(ChainEnum::BridgeHub, &encoded),
+            (pallet.into(), name.into())
+        );
+    }
+
+    #[test]
+    fn encodes_asset_updates() {
+        let params = update_asset_args();
+        assert_asset_hub_call(
+            &make_asset_sufficient(&params),
+            "ForeignAssets",
+            "force_asset_status",
+        );
+        assert_asset_hub_call(
+            &force_set_metadata(&params),
+            "ForeignAssets",
+            "force_set_metadata",
+        );
+    }
+
+    #[test]
+    fn encodes_erc20_registrations() {
+        let asset = Erc20Asset {
+            contract: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".into(),
+            min_balance: 1,
+            is_sufficient: false,
+            name: Some("Wrapped Ether".into()),
+            symbol: Some("WETH".into()),
+            decimals: Some(18),
+        };
+        let calls = register_erc20_asset(&asset).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_asset_hub_call(&calls[0], "ForeignAssets", "force_create");
+        assert_asset_hub_call(&calls[1], "ForeignAssets", "force_set_metadata");
+
+        let asset = Erc20Asset {
+            name: None,
+            symbol: None,
+            decimals: None,
+            ..asset
+        };
+        assert_eq!(register_erc20_asset(&asset).unwrap().len(), 1);
+
+        let asset = Erc20Asset {
+            name: Some("Wrapped Ether".into()),
+            ..asset
+        };
+        assert!(register_erc20_asset(&asset).is_err());
+    }
+
+    #[test]
+    fn encodes_upgrade_authorizations() {
+        let code_hash = [7u8; 32];
+        assert_bridge_hub_call(
+            &bridge_hub_authorize_upgrade(code_hash),
+            "System",
+            "authorize_upgrade",
+        );
+        assert_asset_hub_call(
+            &asset_hub_authorize_upgrade(code_hash),
+            "System",
+            "authorize_upgrade",
+        );
+    }
 }
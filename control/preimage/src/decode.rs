@@ -0,0 +1,711 @@
+use crate::network::{with_network, Network};
+use crate::ChainEnum;
+use alloy_primitives::{Address, U256};
+use codec::Decode;
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use sp_crypto_hashing::blake2_256;
+use std::path::Path;
+use subxt::ext::scale_value::{scale::decode_as_type, Composite, Primitive, Value, ValueDef};
+use subxt::Metadata;
+
+/// Byte strings longer than this, such as runtime code, are shown by their length and hash
+const MAX_BYTES_LEN: usize = 128;
+
+/// Values are split over several lines when they don't fit in this width
+const LINE_WIDTH: usize = 100;
+
+pub type Fields = Vec<(Option<String>, Arg)>;
+
+/// A call decoded with the metadata of the chain it is dispatched on
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call {
+    pub chain: ChainEnum,
+    pub pallet: String,
+    pub name: String,
+    pub args: Fields,
+}
+
+/// Argument of a decoded call, with leaf values formatted for display
+#[derive(Clone, Debug, PartialEq)]
+pub enum Arg {
+    Value(String),
+    Call(Call),
+    Variant(String, Fields),
+    Composite(Fields),
+    Sequence(Vec<Arg>),
+}
+
+/// Read a preimage written by this tool, in either hex or binary format
+pub fn read_preimage(path: &Path) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let data =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    match std::str::from_utf8(&data) {
+        Ok(text) if text.trim().starts_with("0x") => Ok(hex::decode(&text.trim()[2..])?),
+        _ => Ok(data),
+    }
+}
+
+/// Decodes relay chain calls using the bundled metadata of a network, following the calls
+/// sent to BridgeHub and AssetHub in XCM `Transact` instructions.
+pub struct Decoder {
+    network: Network,
+    relay: Metadata,
+    bridge_hub: Metadata,
+    asset_hub: Metadata,
+}
+
+// Where in the call tree a value is converted
+struct Scope<'a> {
+    chain: ChainEnum,
+    pallet: &'a str,
+    call: &'a str,
+    // Chain that XCM messages in the arguments of the call are sent to
+    destination: Option<ChainEnum>,
+}
+
+impl Decoder {
+    pub fn new(
+        network: Network,
+        relay: &[u8],
+        bridge_hub: &[u8],
+        asset_hub: &[u8],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Decoder {
+            network,
+            relay: Metadata::decode(&mut &relay[..])?,
+            bridge_hub: Metadata::decode(&mut &bridge_hub[..])?,
+            asset_hub: Metadata::decode(&mut &asset_hub[..])?,
+        })
+    }
+
+    /// Decode a relay chain call, such as a preimage built by this tool
+    pub fn decode(&self, preimage: &[u8]) -> Result<Call, String> {
+        self.call(ChainEnum::Relay, preimage)
+    }
+
+    /// Convert a value decoded with the metadata of a chain, with calls and events shown by
+    /// pallet and name
+    pub fn value(&self, chain: ChainEnum, value: &Value<u32>) -> Result<Arg, String> {
+        let scope = Scope {
+            chain,
+            pallet: "",
+            call: "",
+            destination: None,
+        };
+        self.arg(&scope, value, None)
+    }
+
+    /// Chain with the parachain ID in an XCM location
+    pub fn destination(&self, location: &Value<u32>) -> Option<ChainEnum> {
+        match parachain(location)? {
+            id if id == self.network.bridge_hub_id() as u128 => Some(ChainEnum::BridgeHub),
+            id if id == self.network.asset_hub_id() as u128 => Some(ChainEnum::AssetHub),
+            _ => None,
+        }
+    }
+
+    pub fn metadata(&self, chain: ChainEnum) -> &Metadata {
+        match chain {
+            ChainEnum::Relay => &self.relay,
+            ChainEnum::BridgeHub => &self.bridge_hub,
+            ChainEnum::AssetHub => &self.asset_hub,
+        }
+    }
+
+    fn call(&self, chain: ChainEnum, data: &[u8]) -> Result<Call, String> {
+        let metadata = self.metadata(chain);
+        let mut input = data;
+        let value = decode_as_type(
+            &mut input,
+            metadata.outer_enums().call_enum_ty(),
+            metadata.types(),
+        )
+        .map_err(|e| format!("Failed to decode {chain} call: {e}"))?;
+        if !input.is_empty() {
+            return Err(format!(
+                "{} bytes left over after decoding {chain} call",
+                input.len()
+            ));
+        }
+        self.call_value(chain, &value)
+    }
+
+    // Events are decoded like calls, which they are shown as
+    fn call_value(&self, chain: ChainEnum, value: &Value<u32>) -> Result<Call, String> {
+        // RuntimeCall and RuntimeEvent have a variant for each pallet, wrapping an enum of the
+        // pallet's calls or events
+        let (pallet, call) = match &value.value {
+            ValueDef::Variant(pallet) => match pallet.values.values().next() {
+                Some(Value {
+                    value: ValueDef::Variant(call),
+                    ..
+                }) => Some((pallet, call)),
+                _ => None,
+            },
+            _ => None,
+        }
+        .ok_or_else(|| format!("Not a {chain} call"))?;
+
+        let destination = match &call.values {
+            Composite::Named(fields) => fields
+                .iter()
+                .find(|(name, _)| name == "dest")
+                .and_then(|(_, dest)| self.destination(dest)),
+            Composite::Unnamed(_) => None,
+        };
+        let scope = Scope {
+            chain,
+            pallet: &pallet.name,
+            call: &call.name,
+            destination,
+        };
+
+        Ok(Call {
+            chain,
+            pallet: pallet.name.clone(),
+            name: call.name.clone(),
+            args: self.fields(&scope, &call.values)?,
+        })
+    }
+
+    fn fields(&self, scope: &Scope, values: &Composite<u32>) -> Result<Fields, String> {
+        let fields: Vec<(Option<&String>, &Value<u32>)> = match values {
+            Composite::Named(fields) => fields.iter().map(|(name, v)| (Some(name), v)).collect(),
+            Composite::Unnamed(values) => values.iter().map(|v| (None, v)).collect(),
+        };
+        fields
+            .into_iter()
+            .map(|(name, value)| {
+                let unit = name.and_then(|name| unit(scope.pallet, scope.call, name));
+                Ok((name.cloned(), self.arg(scope, value, unit)?))
+            })
+            .collect()
+    }
+
+    // Amounts are shown in the unit of the field they are passed in, which newtypes and options
+    // wrapping them keep
+    fn arg(&self, scope: &Scope, value: &Value<u32>, unit: Option<Unit>) -> Result<Arg, String> {
+        let metadata = self.metadata(scope.chain);
+        let outer_enums = metadata.outer_enums();
+        if [outer_enums.call_enum_ty(), outer_enums.event_enum_ty()].contains(&value.context) {
+            return Ok(Arg::Call(self.call_value(scope.chain, value)?));
+        }
+        let types = metadata.types();
+        let ty = types.resolve(value.context);
+        let ident = ty.and_then(|ty| ty.path.ident());
+
+        match &value.value {
+            ValueDef::Composite(composite) => {
+                if let Some((data, is_array)) = bytes(types, value) {
+                    return Ok(Arg::Value(format_bytes(&data, is_array)));
+                }
+                let inner = composite.values().next();
+                match ident.as_deref() {
+                    // Dispatch errors of pallets are shown by name, as in `XcmPallet.SendFailure`
+                    Some("ModuleError") => {
+                        if let Some(error) = self.module_error(scope.chain, composite) {
+                            return Ok(Arg::Value(error));
+                        }
+                    }
+                    Some("AccountId32") => {
+                        if let Some((data, _)) = inner.and_then(|inner| bytes(types, inner)) {
+                            return Ok(Arg::Value(self.format_account(&data)));
+                        }
+                    }
+                    Some("DoubleEncoded") => {
+                        if let Some((data, _)) = inner.and_then(|inner| bytes(types, inner)) {
+                            return Ok(self.double_encoded(scope, &data));
+                        }
+                    }
+                    // 256-bit integers from primitive-types are stored as little-endian u64 limbs
+                    Some("U256") => {
+                        if let Some(limbs) = inner.and_then(limbs) {
+                            return Ok(Arg::Value(
+                                self.format_amount(U256::from_limbs(limbs), unit),
+                            ));
+                        }
+                    }
+                    Some("FixedU128") => {
+                        if let Some(value) = inner.and_then(Value::as_u128) {
+                            return Ok(Arg::Value(with_network!(self.network, |runtime| {
+                                runtime::helpers::units(U256::from(value), 18)
+                            })));
+                        }
+                    }
+                    _ => {}
+                }
+                match (ty.map(|ty| &ty.type_def), composite) {
+                    (Some(TypeDef::Sequence(_) | TypeDef::Array(_)), _) => Ok(Arg::Sequence(
+                        composite
+                            .values()
+                            .map(|item| self.arg(scope, item, None))
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    // Newtypes such as BoundedVec or H256 are shown as the value they wrap
+                    (_, Composite::Unnamed(values)) if values.len() == 1 => {
+                        self.arg(scope, &values[0], unit)
+                    }
+                    _ => Ok(Arg::Composite(self.fields(scope, composite)?)),
+                }
+            }
+            ValueDef::Variant(variant) => {
+                if ident.as_deref() == Some("Option") {
+                    return match variant.values.values().next() {
+                        Some(inner) => self.arg(scope, inner, unit),
+                        None => Ok(Arg::Value("None".into())),
+                    };
+                }
+                Ok(Arg::Variant(
+                    variant.name.clone(),
+                    self.fields(scope, &variant.values)?,
+                ))
+            }
+            ValueDef::Primitive(primitive) => {
+                Ok(Arg::Value(self.format_primitive(primitive, unit)))
+            }
+            ValueDef::BitSequence(bits) => Ok(Arg::Value(format!("{bits:?}"))),
+        }
+    }
+
+    fn module_error(&self, chain: ChainEnum, error: &Composite<u32>) -> Option<String> {
+        let Composite::Named(fields) = error else {
+            return None;
+        };
+        let field = |name: &str| fields.iter().find(|(field, _)| field == name);
+        let index = field("index")?.1.as_u128()?;
+        let (_, error) = field("error")?;
+        let error = match &error.value {
+            ValueDef::Composite(bytes) => bytes.values().next()?.as_u128()?,
+            _ => error.as_u128()?,
+        };
+        let pallet = self.metadata(chain).pallet_by_index(index as u8)?;
+        let variant = pallet.error_variant_by_index(error as u8)?;
+        Some(format!("{}.{}", pallet.name(), variant.name))
+    }
+
+    // The call in a `Transact` instruction, decoded for the chain the message is sent to
+    fn double_encoded(&self, scope: &Scope, data: &[u8]) -> Arg {
+        let encoded = format!("0x{}", hex::encode(data));
+        match scope.destination {
+            Some(chain) => match self.call(chain, data) {
+                Ok(call) => Arg::Call(call),
+                Err(e) => Arg::Value(format!("{encoded} ({e})")),
+            },
+            None => Arg::Value(format!("{encoded} (unknown destination)")),
+        }
+    }
+
+    fn format_primitive(&self, primitive: &Primitive, unit: Option<Unit>) -> String {
+        match primitive {
+            Primitive::U128(value) => self.format_amount(U256::from(*value), unit),
+            Primitive::I128(value) => value.to_string(),
+            Primitive::Bool(value) => value.to_string(),
+            Primitive::Char(value) => format!("{value:?}"),
+            Primitive::String(value) => format!("{value:?}"),
+            Primitive::U256(value) => self.format_amount(U256::from_le_bytes(*value), unit),
+            Primitive::I256(value) => format!("0x{}", hex::encode(value)),
+        }
+    }
+
+    fn format_amount(&self, amount: U256, unit: Option<Unit>) -> String {
+        with_network!(self.network, |runtime| match unit {
+            Some(Unit::Native) => match u128::try_from(amount) {
+                Ok(amount) => runtime::helpers::native(amount),
+                Err(_) => amount.to_string(),
+            },
+            Some(Unit::Ether) => runtime::helpers::ether(amount),
+            Some(Unit::Gwei) => runtime::helpers::gwei(amount),
+            None => amount.to_string(),
+        })
+    }
+
+    fn format_account(&self, data: &[u8]) -> String {
+        match <[u8; 32]>::try_from(data) {
+            Ok(account) => AccountId32::from(account)
+                .to_ss58check_with_version(Ss58AddressFormat::custom(self.network.ss58_prefix())),
+            Err(_) => format!("0x{}", hex::encode(data)),
+        }
+    }
+}
+
+impl Call {
+    /// Render the call as a tree of nested calls and their arguments
+    pub fn tree(&self) -> String {
+        let mut out = String::new();
+        write_call(&mut out, "", self, ChainEnum::Relay, 0);
+        out
+    }
+
+    /// Render the call as JSON, with the arguments in the format taken by the `call` command
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "chain": self.chain,
+            "pallet": self.pallet,
+            "call": self.name,
+            "args": fields_json(&self.args),
+        })
+    }
+}
+
+impl Arg {
+    /// Render the argument on one line if it fits, otherwise as a tree. Calls are labelled
+    /// with their chain when it differs from `chain`.
+    pub fn tree(&self, chain: ChainEnum) -> String {
+        let mut out = String::new();
+        write_arg(&mut out, "", self, chain, 0);
+        out
+    }
+
+    /// Render the argument as JSON. Enums are written as `"Variant"` or `{"Variant": fields}`,
+    /// and leaf values as they are displayed.
+    pub fn json(&self) -> serde_json::Value {
+        match self {
+            Arg::Value(text) => text.as_str().into(),
+            Arg::Call(call) => call.json(),
+            Arg::Variant(name, fields) if fields.is_empty() => name.as_str().into(),
+            Arg::Variant(name, fields) => {
+                let mut variant = serde_json::Map::new();
+                variant.insert(name.clone(), fields_json(fields));
+                variant.into()
+            }
+            Arg::Composite(fields) => fields_json(fields),
+            Arg::Sequence(items) => items.iter().map(Arg::json).collect(),
+        }
+    }
+}
+
+/// Render fields as a tree, one per line
+pub fn fields_tree(fields: &Fields, chain: ChainEnum) -> String {
+    let mut out = String::new();
+    write_fields(&mut out, fields, chain, 0);
+    out
+}
+
+// Named fields as an object, and unnamed fields as an array unless there is only one
+fn fields_json(fields: &Fields) -> serde_json::Value {
+    match &fields[..] {
+        [(None, arg)] => arg.json(),
+        [(Some(_), _), ..] => fields
+            .iter()
+            .map(|(name, arg)| (name.clone().unwrap_or_default(), arg.json()))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        _ => fields.iter().map(|(_, arg)| arg.json()).collect(),
+    }
+}
+
+// Bytes of a sequence or array of u8, and whether it has a fixed length
+fn bytes(types: &PortableRegistry, value: &Value<u32>) -> Option<(Vec<u8>, bool)> {
+    let (item_type, is_array) = match &types.resolve(value.context)?.type_def {
+        TypeDef::Sequence(def) => (def.type_param.id, false),
+        TypeDef::Array(def) => (def.type_param.id, true),
+        _ => return None,
+    };
+    if !matches!(
+        types.resolve(item_type)?.type_def,
+        TypeDef::Primitive(TypeDefPrimitive::U8)
+    ) {
+        return None;
+    }
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+    let data = composite
+        .values()
+        .map(|byte| byte.as_u128().map(|byte| byte as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    Some((data, is_array))
+}
+
+fn limbs(value: &Value<u32>) -> Option<[u64; 4]> {
+    let ValueDef::Composite(composite) = &value.value else {
+        return None;
+    };
+    let limbs = composite
+        .values()
+        .map(|limb| limb.as_u128().map(|limb| limb as u64))
+        .collect::<Option<Vec<u64>>>()?;
+    limbs.try_into().ok()
+}
+
+// ID of the parachain in an XCM location
+fn parachain(value: &Value<u32>) -> Option<u128> {
+    match &value.value {
+        ValueDef::Variant(variant) if variant.name == "Parachain" => {
+            variant.values.values().next().and_then(Value::as_u128)
+        }
+        ValueDef::Variant(variant) => variant.values.values().find_map(parachain),
+        ValueDef::Composite(composite) => composite.values().find_map(parachain),
+        _ => None,
+    }
+}
+
+// Unit of the amounts passed in a field of a call. Amounts are only shown in a unit where the
+// field is known to hold one, as other balances, such as those of `ForeignAssets` or
+// `Treasury.spend`, are in other assets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Unit {
+    Native,
+    Ether,
+    Gwei,
+}
+
+fn unit(pallet: &str, call: &str, field: &str) -> Option<Unit> {
+    match (pallet, call, field) {
+        ("Balances", _, "value" | "amount" | "new_free" | "delta") => Some(Unit::Native),
+        ("Treasury", "spend_local", "amount") => Some(Unit::Native),
+        ("EthereumSystem", _, "local" | "create_asset_xcm" | "transfer_asset_xcm") => {
+            Some(Unit::Native)
+        }
+        ("EthereumSystem", _, "remote" | "register_token" | "amount") => Some(Unit::Ether),
+        ("EthereumSystem", _, "fee_per_gas") => Some(Unit::Gwei),
+        _ => None,
+    }
+}
+
+fn format_bytes(data: &[u8], is_array: bool) -> String {
+    let is_text = !data.is_empty() && data.iter().all(|b| b.is_ascii_graphic() || *b == b' ');
+    match data.len() {
+        _ if is_text && !is_array => format!("{:?}", String::from_utf8_lossy(data)),
+        20 => Address::from_slice(data).to_checksum(None),
+        len if len > MAX_BYTES_LEN => format!(
+            "<{len} bytes, blake2_256 0x{}>",
+            hex::encode(blake2_256(data))
+        ),
+        _ => format!("0x{}", hex::encode(data)),
+    }
+}
+
+fn write_line(out: &mut String, depth: usize, text: &str) {
+    out.push_str(&"  ".repeat(depth));
+    out.push_str(text);
+    out.push('\n');
+}
+
+fn write_call(out: &mut String, prefix: &str, call: &Call, parent: ChainEnum, depth: usize) {
+    let chain = match call.chain {
+        chain if chain != parent => format!("[{chain}] "),
+        _ => String::new(),
+    };
+    write_line(
+        out,
+        depth,
+        &format!("{prefix}{chain}{}.{}", call.pallet, call.name),
+    );
+    write_fields(out, &call.args, call.chain, depth + 1);
+}
+
+fn write_fields(
+    out: &mut String,
+    fields: &[(Option<String>, Arg)],
+    chain: ChainEnum,
+    depth: usize,
+) {
+    for (name, arg) in fields {
+        let prefix = match name {
+            Some(name) => format!("{name}: "),
+            None => "- ".into(),
+        };
+        write_arg(out, &prefix, arg, chain, depth);
+    }
+}
+
+// Write an argument on one line if it fits, otherwise with its parts on the following lines
+fn write_arg(out: &mut String, prefix: &str, arg: &Arg, chain: ChainEnum, depth: usize) {
+    if let Some(text) =
+        inline(arg).filter(|text| depth * 2 + prefix.len() + text.len() <= LINE_WIDTH)
+    {
+        return write_line(out, depth, &format!("{prefix}{text}"));
+    }
+    match arg {
+        Arg::Value(text) => write_line(out, depth, &format!("{prefix}{text}")),
+        Arg::Call(call) => write_call(out, prefix, call, chain, depth),
+        Arg::Variant(name, fields) => {
+            write_line(out, depth, &format!("{prefix}{name}"));
+            match &fields[..] {
+                [(None, arg)] => write_arg(out, "", arg, chain, depth + 1),
+                fields => write_fields(out, fields, chain, depth + 1),
+            }
+        }
+        Arg::Composite(fields) => {
+            let depth = write_header(out, prefix, depth);
+            write_fields(out, fields, chain, depth);
+        }
+        Arg::Sequence(items) => {
+            let depth = write_header(out, prefix, depth);
+            for item in items {
+                write_arg(out, "- ", item, chain, depth);
+            }
+        }
+    }
+}
+
+// Write the prefix of a value split over several lines, returning the depth of its parts.
+// Without a prefix, the parts continue at the same depth.
+fn write_header(out: &mut String, prefix: &str, depth: usize) -> usize {
+    match prefix.trim_end() {
+        "" => depth,
+        header => {
+            write_line(out, depth, header);
+            depth + 1
+        }
+    }
+}
+
+fn inline(arg: &Arg) -> Option<String> {
+    match arg {
+        Arg::Value(text) => Some(text.clone()),
+        Arg::Call(_) => None,
+        Arg::Variant(name, fields) if fields.is_empty() => Some(name.clone()),
+        Arg::Variant(name, fields) => match inline_fields(fields)? {
+            fields if fields.starts_with('{') => Some(format!("{name} {fields}")),
+            fields => Some(format!("{name}{fields}")),
+        },
+        Arg::Composite(fields) => inline_fields(fields),
+        Arg::Sequence(items) => {
+            let items = items.iter().map(inline).collect::<Option<Vec<_>>>()?;
+            Some(format!("[{}]", items.join(", ")))
+        }
+    }
+}
+
+fn inline_fields(fields: &Fields) -> Option<String> {
+    let items = fields
+        .iter()
+        .map(|(name, arg)| match name {
+            Some(name) => Some(format!("{name}: {}", inline(arg)?)),
+            None => inline(arg),
+        })
+        .collect::<Option<Vec<_>>>()?
+        .join(", ");
+    match fields.first() {
+        Some((Some(_), _)) => Some(format!("{{ {items} }}")),
+        _ => Some(format!("({items})")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::polkadot::{bridge_hub_runtime, relay_runtime};
+    use codec::Encode;
+    use relay_runtime::runtime_types::{
+        pallet_balances, pallet_utility, pallet_xcm,
+        sp_weights::weight_v2::Weight,
+        staging_xcm::v4::{
+            junction::Junction, junctions::Junctions, location::Location, Instruction, Xcm,
+        },
+        xcm::double_encoded::DoubleEncoded,
+        xcm::v3::{OriginKind, WeightLimit},
+        xcm::{VersionedLocation, VersionedXcm},
+    };
+    use subxt::utils::MultiAddress;
+
+    // A batch transferring 1.5 DOT on the relay chain, and setting the token transfer fees on
+    // BridgeHub in a `Transact`
+    fn preimage() -> Vec<u8> {
+        let set_token_transfer_fees = bridge_hub_runtime::RuntimeCall::EthereumSystem(
+            bridge_hub_runtime::runtime_types::snowbridge_pallet_system::pallet::Call::set_token_transfer_fees {
+                create_asset_xcm: 2_000_000_000,
+                transfer_asset_xcm: 100_000_000,
+                register_token: bridge_hub_runtime::runtime_types::primitive_types::U256(
+                    U256::from(5_000_000_000_000_000u64).into_limbs(),
+                ),
+            },
+        );
+        let transfer = relay_runtime::RuntimeCall::Balances(
+            pallet_balances::pallet::Call::transfer_keep_alive {
+                dest: MultiAddress::Id([1u8; 32].into()),
+                value: 15_000_000_000,
+            },
+        );
+        let send = relay_runtime::RuntimeCall::XcmPallet(pallet_xcm::pallet::Call::send {
+            dest: Box::new(VersionedLocation::V4(Location {
+                parents: 0,
+                interior: Junctions::X1([Junction::Parachain(1002)]),
+            })),
+            message: Box::new(VersionedXcm::V4(Xcm(vec![
+                Instruction::UnpaidExecution {
+                    weight_limit: WeightLimit::Unlimited,
+                    check_origin: None,
+                },
+                Instruction::Transact {
+                    origin_kind: OriginKind::Superuser,
+                    require_weight_at_most: Weight {
+                        ref_time: 1_000_000_000,
+                        proof_size: 10_000,
+                    },
+                    call: DoubleEncoded {
+                        encoded: set_token_transfer_fees.encode(),
+                    },
+                },
+            ]))),
+        });
+        relay_runtime::RuntimeCall::Utility(pallet_utility::pallet::Call::batch_all {
+            calls: vec![transfer, send],
+        })
+        .encode()
+    }
+
+    #[test]
+    fn decodes_calls_transacted_on_bridge_hub() {
+        let decoder = crate::decoder(Network::Polkadot).unwrap();
+        let call = decoder.decode(&preimage()).unwrap();
+
+        assert_eq!(
+            call.tree(),
+            r#"Utility.batch_all
+  calls:
+    - Balances.transfer_keep_alive
+      dest: Id(12KM5KYi2fBdRoijHVrpPx71buoU5bG8Yq7rVpEG7nrUG6f)
+      value: 1.5000000000 DOT [15000000000 PLANCK]
+    - XcmPallet.send
+      dest: V4({ parents: 0, interior: X1([Parachain(1002)]) })
+      message: V4
+        - UnpaidExecution { weight_limit: Unlimited, check_origin: None }
+        - Transact
+          origin_kind: Superuser
+          require_weight_at_most: { ref_time: 1000000000, proof_size: 10000 }
+          call: [BridgeHub] EthereumSystem.set_token_transfer_fees
+            create_asset_xcm: 0.2000000000 DOT [2000000000 PLANCK]
+            transfer_asset_xcm: 0.0100000000 DOT [100000000 PLANCK]
+            register_token: 0.005000000000000000 ETH [5000000000000000 WEI]
+"#
+        );
+    }
+
+    #[test]
+    fn rejects_bytes_left_over() {
+        let mut preimage = preimage();
+        preimage.push(0);
+        let decoder = crate::decoder(Network::Polkadot).unwrap();
+        assert_eq!(
+            decoder.decode(&preimage),
+            Err("1 bytes left over after decoding Relay call".into())
+        );
+    }
+
+    #[test]
+    fn shows_units_only_for_known_fields() {
+        assert_eq!(
+            unit("Balances", "transfer_keep_alive", "value"),
+            Some(Unit::Native)
+        );
+        assert_eq!(
+            unit("Treasury", "spend_local", "amount"),
+            Some(Unit::Native)
+        );
+        // Amounts of `Treasury.spend` are in the asset given with them
+        assert_eq!(unit("Treasury", "spend", "amount"), None);
+        assert_eq!(unit("ForeignAssets", "force_create", "min_balance"), None);
+        assert_eq!(
+            unit("EthereumSystem", "set_pricing_parameters", "remote"),
+            Some(Unit::Ether)
+        );
+        assert_eq!(
+            unit("EthereumSystem", "set_pricing_parameters", "fee_per_gas"),
+            Some(Unit::Gwei)
+        );
+    }
+}
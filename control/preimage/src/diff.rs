@@ -0,0 +1,500 @@
+use crate::decode::{Arg, Call, Fields};
+use crate::ChainEnum;
+use serde::Serialize;
+
+/// A difference between two decoded preimages, at a path of call, field and item names
+#[derive(Debug, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    Added {
+        path: String,
+        value: String,
+    },
+    Removed {
+        path: String,
+        value: String,
+    },
+    Changed {
+        path: String,
+        old: String,
+        new: String,
+        // Difference between integers, such as the weights in `require_weight_at_most`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        delta: Option<i128>,
+    },
+}
+
+/// Compare the calls of two preimages. Calls in batches and instructions in XCM messages are
+/// matched by name, and XCM sends by their destination and transacted calls, so that inserting
+/// a call is reported as one addition.
+pub fn diff(a: &Call, b: &Call) -> Vec<Change> {
+    let mut changes = vec![];
+    diff_calls(&mut changes, "", ChainEnum::Relay, a, b);
+    changes
+}
+
+fn diff_calls(changes: &mut Vec<Change>, path: &str, parent: ChainEnum, a: &Call, b: &Call) {
+    if (a.chain, &a.pallet, &a.name) == (b.chain, &b.pallet, &b.name) {
+        let path = join(path, &call_label(a, parent));
+        diff_fields(changes, &path, a.chain, &a.args, &b.args);
+    } else {
+        // The preimages themselves are different calls
+        let path = match path {
+            "" => "call",
+            path => path,
+        };
+        let (a, b) = (Arg::Call(a.clone()), Arg::Call(b.clone()));
+        changes.push(changed(path, parent, &a, &b));
+    }
+}
+
+fn diff_arg(changes: &mut Vec<Change>, path: &str, chain: ChainEnum, a: &Arg, b: &Arg) {
+    if a == b {
+        return;
+    }
+    match (a, b) {
+        (Arg::Call(a), Arg::Call(b)) => diff_calls(changes, path, chain, a, b),
+        (Arg::Variant(name, a), Arg::Variant(other, b)) if name == other => {
+            diff_fields(changes, &join(path, name), chain, a, b)
+        }
+        (Arg::Composite(a), Arg::Composite(b)) => diff_fields(changes, path, chain, a, b),
+        (Arg::Sequence(a), Arg::Sequence(b)) => diff_sequence(changes, path, chain, a, b),
+        _ => changes.push(changed(path, chain, a, b)),
+    }
+}
+
+fn diff_fields(changes: &mut Vec<Change>, path: &str, chain: ChainEnum, a: &Fields, b: &Fields) {
+    // A single unnamed field, as in a newtype or XCM version, adds nothing to the path
+    let single = a.len() == 1 && b.len() == 1;
+    for i in 0..a.len().max(b.len()) {
+        let label = match a.get(i).or(b.get(i)) {
+            Some((Some(name), _)) => name.clone(),
+            _ if single => String::new(),
+            _ => i.to_string(),
+        };
+        let path = join(path, &label);
+        match (a.get(i), b.get(i)) {
+            (Some((_, a)), Some((_, b))) => diff_arg(changes, &path, chain, a, b),
+            (Some((_, a)), None) => changes.push(Change::Removed {
+                path,
+                value: render(a, chain),
+            }),
+            (None, Some((_, b))) => changes.push(Change::Added {
+                path,
+                value: render(b, chain),
+            }),
+            (None, None) => {}
+        }
+    }
+}
+
+// Align the items of two sequences on their longest common subsequence of keys, then compare
+// the aligned items and report the rest as removed or added
+fn diff_sequence(changes: &mut Vec<Change>, path: &str, chain: ChainEnum, a: &[Arg], b: &[Arg]) {
+    let keys_a: Vec<String> = a.iter().map(|arg| key(arg, chain)).collect();
+    let keys_b: Vec<String> = b.iter().map(|arg| key(arg, chain)).collect();
+
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if keys_a[i] == keys_b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && keys_a[i] == keys_b[j] {
+            diff_arg(changes, &format!("{path}[{j}]"), chain, &a[i], &b[j]);
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            changes.push(Change::Removed {
+                path: format!("{path}[{i}]"),
+                value: render(&a[i], chain),
+            });
+            i += 1;
+        } else {
+            changes.push(Change::Added {
+                path: format!("{path}[{j}]"),
+                value: render(&b[j], chain),
+            });
+            j += 1;
+        }
+    }
+}
+
+// Items of a sequence are matched by call or variant name, and other values by their content.
+// Calls sending XCM are also matched by their destination and the calls they transact, and
+// `Transact` instructions by their call, so that messages to different chains are told apart.
+fn key(arg: &Arg, chain: ChainEnum) -> String {
+    match arg {
+        Arg::Call(call) => {
+            let mut key = call_label(call, chain);
+            let dest = call
+                .args
+                .iter()
+                .find(|(name, _)| name.as_deref() == Some("dest"));
+            if let Some(id) = dest.and_then(|(_, dest)| parachain(dest)) {
+                key.push_str(&format!(" -> {id}"));
+            }
+            let mut calls = vec![];
+            for (_, arg) in &call.args {
+                transacted(arg, call.chain, &mut calls);
+            }
+            for label in calls {
+                key.push_str(&format!(" / {label}"));
+            }
+            key
+        }
+        Arg::Variant(name, fields) if name == "Transact" => match transact_call(fields) {
+            Some(call) => format!("{name} {}", call_label(call, chain)),
+            None => name.clone(),
+        },
+        Arg::Variant(name, _) => name.clone(),
+        arg => render(arg, chain),
+    }
+}
+
+// Parachain ID of a decoded XCM location
+fn parachain(arg: &Arg) -> Option<&str> {
+    match arg {
+        Arg::Variant(name, fields) if name == "Parachain" => match &fields[..] {
+            [(_, Arg::Value(id))] => Some(id),
+            _ => None,
+        },
+        Arg::Variant(_, fields) | Arg::Composite(fields) => {
+            fields.iter().find_map(|(_, arg)| parachain(arg))
+        }
+        Arg::Sequence(items) => items.iter().find_map(parachain),
+        _ => None,
+    }
+}
+
+// Labels of the calls in the `Transact` instructions of a message
+fn transacted(arg: &Arg, chain: ChainEnum, calls: &mut Vec<String>) {
+    match arg {
+        Arg::Variant(name, fields) if name == "Transact" => {
+            if let Some(call) = transact_call(fields) {
+                calls.push(call_label(call, chain));
+            }
+        }
+        Arg::Variant(_, fields) | Arg::Composite(fields) => {
+            for (_, arg) in fields {
+                transacted(arg, chain, calls);
+            }
+        }
+        Arg::Sequence(items) => {
+            for item in items {
+                transacted(item, chain, calls);
+            }
+        }
+        Arg::Call(_) | Arg::Value(_) => {}
+    }
+}
+
+fn transact_call(fields: &Fields) -> Option<&Call> {
+    fields
+        .iter()
+        .find_map(|(name, arg)| match (name.as_deref(), arg) {
+            (Some("call"), Arg::Call(call)) => Some(call),
+            _ => None,
+        })
+}
+
+fn changed(path: &str, chain: ChainEnum, a: &Arg, b: &Arg) -> Change {
+    let (old, new) = (render(a, chain), render(b, chain));
+    let delta = match (old.parse::<i128>(), new.parse::<i128>()) {
+        (Ok(old), Ok(new)) => new.checked_sub(old),
+        _ => None,
+    };
+    Change::Changed {
+        path: path.to_owned(),
+        old,
+        new,
+        delta,
+    }
+}
+
+fn call_label(call: &Call, parent: ChainEnum) -> String {
+    match call.chain {
+        chain if chain != parent => format!("[{chain}] {}.{}", call.pallet, call.name),
+        _ => format!("{}.{}", call.pallet, call.name),
+    }
+}
+
+fn render(arg: &Arg, chain: ChainEnum) -> String {
+    arg.tree(chain).trim_end().to_owned()
+}
+
+fn join(path: &str, label: &str) -> String {
+    match (path, label) {
+        (path, "") => path.to_owned(),
+        ("", label) => label.to_owned(),
+        (path, label) => format!("{path}/{label}"),
+    }
+}
+
+/// Render changes as text, one per line, with values spanning several lines indented below.
+/// Additions are marked `+`, removals `-` and changes `~`, optionally coloured with ANSI codes.
+pub fn text(changes: &[Change], color: bool) -> String {
+    let paint = |code: &str, text: String| {
+        if color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text
+        }
+    };
+    let mut out = String::new();
+    for change in changes {
+        match change {
+            Change::Added { path, value } => {
+                out.push_str(&paint("32", marked('+', &format!("{path}: {value}"))));
+            }
+            Change::Removed { path, value } => {
+                out.push_str(&paint("31", marked('-', &format!("{path}: {value}"))));
+            }
+            Change::Changed {
+                path,
+                old,
+                new,
+                delta,
+            } => {
+                let delta = match delta {
+                    Some(delta) => format!(" ({delta:+})"),
+                    None => String::new(),
+                };
+                if old.contains('\n') || new.contains('\n') {
+                    out.push_str(&paint("33", marked('~', &format!("{path}:{delta}"))));
+                    out.push_str(&paint("31", marked('-', old)));
+                    out.push_str(&paint("32", marked('+', new)));
+                } else {
+                    let line = format!("{path}: {old} -> {new}{delta}");
+                    out.push_str(&paint("33", marked('~', &line)));
+                }
+            }
+        }
+    }
+    out
+}
+
+// Prefix each line with the mark, indenting the lines after the first
+fn marked(mark: char, text: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => format!("{mark} {line}\n"),
+            _ => format!("{mark}     {line}\n"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(chain: ChainEnum, pallet: &str, name: &str, args: Fields) -> Call {
+        Call {
+            chain,
+            pallet: pallet.into(),
+            name: name.into(),
+            args,
+        }
+    }
+
+    fn value(text: &str) -> Arg {
+        Arg::Value(text.into())
+    }
+
+    fn field(name: &str, arg: Arg) -> (Option<String>, Arg) {
+        (Some(name.into()), arg)
+    }
+
+    // `XcmPallet.send` of a message transacting `call` on parachain `id`, as decoded
+    fn send(id: &str, ref_time: &str, transacted: Call) -> Arg {
+        let dest = Arg::Variant(
+            "V4".into(),
+            vec![(
+                None,
+                Arg::Composite(vec![
+                    field("parents", value("0")),
+                    field(
+                        "interior",
+                        Arg::Variant(
+                            "X1".into(),
+                            vec![(
+                                None,
+                                Arg::Sequence(vec![Arg::Variant(
+                                    "Parachain".into(),
+                                    vec![(None, value(id))],
+                                )]),
+                            )],
+                        ),
+                    ),
+                ]),
+            )],
+        );
+        let transact = Arg::Variant(
+            "Transact".into(),
+            vec![
+                field("origin_kind", Arg::Variant("Superuser".into(), vec![])),
+                field(
+                    "require_weight_at_most",
+                    Arg::Composite(vec![
+                        field("ref_time", value(ref_time)),
+                        field("proof_size", value("10000")),
+                    ]),
+                ),
+                field("call", Arg::Call(transacted)),
+            ],
+        );
+        let message = Arg::Variant(
+            "V4".into(),
+            vec![(
+                None,
+                Arg::Sequence(vec![
+                    Arg::Variant(
+                        "UnpaidExecution".into(),
+                        vec![
+                            field("weight_limit", Arg::Variant("Unlimited".into(), vec![])),
+                            field("check_origin", value("None")),
+                        ],
+                    ),
+                    transact,
+                ]),
+            )],
+        );
+        Arg::Call(call(
+            ChainEnum::Relay,
+            "XcmPallet",
+            "send",
+            vec![field("dest", dest), field("message", message)],
+        ))
+    }
+
+    fn set_pricing_parameters() -> Call {
+        call(
+            ChainEnum::BridgeHub,
+            "EthereumSystem",
+            "set_pricing_parameters",
+            vec![field("params", value("..."))],
+        )
+    }
+
+    fn set_operating_mode() -> Call {
+        call(
+            ChainEnum::BridgeHub,
+            "EthereumSystem",
+            "set_operating_mode",
+            vec![field("mode", Arg::Variant("Normal".into(), vec![]))],
+        )
+    }
+
+    fn set_storage() -> Call {
+        call(
+            ChainEnum::AssetHub,
+            "System",
+            "set_storage",
+            vec![field("items", value("..."))],
+        )
+    }
+
+    fn batch(calls: Vec<Arg>) -> Call {
+        call(
+            ChainEnum::Relay,
+            "Utility",
+            "force_batch",
+            vec![field("calls", Arg::Sequence(calls))],
+        )
+    }
+
+    fn paths(changes: &[Change]) -> Vec<String> {
+        changes
+            .iter()
+            .map(|change| match change {
+                Change::Added { path, .. } => format!("+ {path}"),
+                Change::Removed { path, .. } => format!("- {path}"),
+                Change::Changed { path, .. } => format!("~ {path}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_an_inserted_send_as_one_addition() {
+        let a = batch(vec![
+            send("1002", "1000", set_pricing_parameters()),
+            send("1000", "1000", set_storage()),
+        ]);
+        let b = batch(vec![
+            send("1002", "1000", set_pricing_parameters()),
+            send("1002", "1000", set_operating_mode()),
+            send("1000", "1000", set_storage()),
+        ]);
+
+        let changes = diff(&a, &b);
+        assert_eq!(paths(&changes), ["+ Utility.force_batch/calls[1]"]);
+    }
+
+    #[test]
+    fn reports_a_removed_send_as_one_removal() {
+        let a = batch(vec![
+            send("1002", "1000", set_operating_mode()),
+            send("1002", "1000", set_pricing_parameters()),
+            send("1000", "1000", set_storage()),
+        ]);
+        let b = batch(vec![
+            send("1002", "1000", set_operating_mode()),
+            send("1000", "1000", set_storage()),
+        ]);
+
+        let changes = diff(&a, &b);
+        assert_eq!(paths(&changes), ["- Utility.force_batch/calls[1]"]);
+    }
+
+    #[test]
+    fn matches_sends_by_destination() {
+        let a = batch(vec![send("1000", "1000", set_storage())]);
+        let b = batch(vec![
+            send("1002", "1000", set_storage()),
+            send("1000", "1000", set_storage()),
+        ]);
+
+        let changes = diff(&a, &b);
+        assert_eq!(paths(&changes), ["+ Utility.force_batch/calls[0]"]);
+    }
+
+    #[test]
+    fn reports_the_delta_of_a_changed_weight() {
+        let a = batch(vec![send("1002", "1000", set_pricing_parameters())]);
+        let b = batch(vec![send("1002", "1500", set_pricing_parameters())]);
+
+        let changes = diff(&a, &b);
+        assert_eq!(changes.len(), 1);
+        let Change::Changed {
+            path,
+            old,
+            new,
+            delta,
+        } = &changes[0]
+        else {
+            panic!("expected a change, got {changes:?}");
+        };
+        assert_eq!(
+            path,
+            "Utility.force_batch/calls[0]/XcmPallet.send/message/V4[1]/Transact/require_weight_at_most/ref_time"
+        );
+        assert_eq!(
+            (old.as_str(), new.as_str(), *delta),
+            ("1000", "1500", Some(500))
+        );
+    }
+
+    #[test]
+    fn reports_different_calls() {
+        let changes = diff(&set_operating_mode(), &set_pricing_parameters());
+        assert_eq!(paths(&changes), ["~ call"]);
+    }
+}
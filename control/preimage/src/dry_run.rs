@@ -0,0 +1,294 @@
+use crate::decode::{fields_tree, Decoder};
+use crate::{ChainEnum, Context};
+use codec::Encode;
+use subxt::ext::scale_value::{
+    scale::{decode_as_type, encode_as_type},
+    Composite, Value, ValueDef,
+};
+
+// Parameter names and types, and output type, of a runtime API method
+struct Signature {
+    inputs: Vec<(String, u32)>,
+    output: u32,
+}
+
+impl Signature {
+    fn input(&self, index: usize) -> Result<&(String, u32), String> {
+        self.inputs
+            .get(index)
+            .ok_or_else(|| format!("Missing parameter {index} of the runtime API method"))
+    }
+}
+
+/// Dry-run the preimage with Root origin on the relay chain, then each XCM it sends to
+/// BridgeHub or AssetHub, printing the results. Returns whether all of them succeeded.
+pub async fn dry_run(
+    context: &Context,
+    decoder: &Decoder,
+    preimage: &[u8],
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let chain = ChainEnum::Relay;
+    let signature = signature(decoder, chain, "dry_run_call")?;
+    let origin = Value::unnamed_variant("system", [Value::unnamed_variant("Root", [])]);
+    let mut params = encode(decoder, chain, signature.input(0)?, &origin)?;
+    params.extend(preimage);
+    // Newer runtimes take the XCM version to convert forwarded messages to
+    if signature.inputs.len() > 2 {
+        params.extend(context.network.xcm_version().encode());
+    }
+    let result = call(context, decoder, chain, "dry_run_call", &signature, &params).await?;
+
+    let Some(effects) = report(decoder, chain, &result)? else {
+        return Ok(false);
+    };
+    let execution_result = field(effects, "execution_result").ok_or("Missing dispatch result")?;
+    let mut success = variant_name(execution_result) == Some("Ok");
+
+    let forwarded = field(effects, "forwarded_xcms")
+        .map(items)
+        .unwrap_or_default();
+    for (destination, messages) in forwarded.into_iter().filter_map(pair) {
+        let Some(chain) = decoder.destination(destination) else {
+            eprintln!("Not dry-running messages sent to a chain other than BridgeHub or AssetHub");
+            continue;
+        };
+        for message in items(messages) {
+            success &= dry_run_xcm(context, decoder, chain, message).await?;
+        }
+    }
+
+    Ok(success)
+}
+
+// Dry-run an XCM sent by the relay chain to a system parachain
+async fn dry_run_xcm(
+    context: &Context,
+    decoder: &Decoder,
+    chain: ChainEnum,
+    message: &Value<u32>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let signature = signature(decoder, chain, "dry_run_xcm")?;
+    let relay = Value::unnamed_variant(
+        "V4",
+        [Value::named_composite([
+            ("parents", Value::u128(1)),
+            ("interior", Value::unnamed_variant("Here", [])),
+        ])],
+    );
+    let mut params = encode(decoder, chain, signature.input(0)?, &relay)?;
+    params.extend(encode(decoder, chain, signature.input(1)?, message)?);
+    let result = call(context, decoder, chain, "dry_run_xcm", &signature, &params).await?;
+
+    Ok(xcm_succeeded(decoder, chain, &result)?)
+}
+
+// Whether the XCM of a `DryRunApi.dry_run_xcm` result executed completely, printing the result
+// and reporting a failed `ExpectTransactStatus`
+fn xcm_succeeded(decoder: &Decoder, chain: ChainEnum, result: &Value<u32>) -> Result<bool, String> {
+    let Some(effects) = report(decoder, chain, result)? else {
+        return Ok(false);
+    };
+    let outcome = field(effects, "execution_result").ok_or("Missing XCM outcome")?;
+    if contains_variant(outcome, "ExpectationFalse") {
+        eprintln!(
+            "ExpectTransactStatus failed on {chain}: the call in the Transact instruction failed"
+        );
+    }
+    Ok(variant_name(outcome) == Some("Complete"))
+}
+
+// Print the execution result and events of a dry run, returning its effects unless the
+// runtime API returned an error
+fn report<'a>(
+    decoder: &Decoder,
+    chain: ChainEnum,
+    result: &'a Value<u32>,
+) -> Result<Option<&'a Value<u32>>, String> {
+    let (name, inner) = match &result.value {
+        ValueDef::Variant(variant) => (variant.name.as_str(), variant.values.values().next()),
+        _ => return Err(format!("Unexpected dry run result on {chain}")),
+    };
+    match (name, inner) {
+        ("Ok", Some(effects)) => {
+            let fields = ["execution_result", "emitted_events"]
+                .into_iter()
+                .filter_map(|name| Some((name, field(effects, name)?)))
+                .map(|(name, value)| Ok((Some(name.to_owned()), decoder.value(chain, value)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            eprintln!("Dry run on {chain}:");
+            eprint!("{}", indent(&fields_tree(&fields, chain)));
+            Ok(Some(effects))
+        }
+        ("Err", Some(error)) => {
+            let error = decoder.value(chain, error)?.tree(chain);
+            eprintln!("Dry run on {chain} failed: {}", error.trim_end());
+            Ok(None)
+        }
+        _ => Err(format!("Unexpected dry run result on {chain}")),
+    }
+}
+
+fn signature(decoder: &Decoder, chain: ChainEnum, method: &str) -> Result<Signature, String> {
+    let metadata = decoder.metadata(chain);
+    let method = metadata
+        .runtime_api_trait_by_name("DryRunApi")
+        .and_then(|api| api.method_by_name(method))
+        .ok_or_else(|| format!("DryRunApi.{method} not found in {chain} metadata"))?;
+    Ok(Signature {
+        inputs: method
+            .inputs()
+            .map(|input| (input.name.clone(), input.ty))
+            .collect(),
+        output: method.output_ty(),
+    })
+}
+
+// Encode a runtime API parameter with the type it has on the chain
+fn encode<T>(
+    decoder: &Decoder,
+    chain: ChainEnum,
+    (name, ty): &(String, u32),
+    value: &Value<T>,
+) -> Result<Vec<u8>, String> {
+    let mut data = vec![];
+    encode_as_type(value, *ty, decoder.metadata(chain).types(), &mut data)
+        .map_err(|e| format!("Failed to encode {name} for {chain}: {e}"))?;
+    Ok(data)
+}
+
+async fn call(
+    context: &Context,
+    decoder: &Decoder,
+    chain: ChainEnum,
+    method: &str,
+    signature: &Signature,
+    params: &[u8],
+) -> Result<Value<u32>, Box<dyn std::error::Error>> {
+    let data = context
+        .api(chain)
+        .backend()
+        .call(
+            &format!("DryRunApi_{method}"),
+            Some(params),
+            context.block(chain).hash,
+        )
+        .await?;
+    let result = decode_as_type(
+        &mut &data[..],
+        signature.output,
+        decoder.metadata(chain).types(),
+    )
+    .map_err(|e| format!("Failed to decode DryRunApi.{method} result on {chain}: {e}"))?;
+    Ok(result)
+}
+
+fn field<'a>(value: &'a Value<u32>, name: &str) -> Option<&'a Value<u32>> {
+    match &value.value {
+        ValueDef::Composite(Composite::Named(fields)) => fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+fn items(value: &Value<u32>) -> Vec<&Value<u32>> {
+    match &value.value {
+        ValueDef::Composite(composite) => composite.values().collect(),
+        _ => vec![],
+    }
+}
+
+fn pair(value: &Value<u32>) -> Option<(&Value<u32>, &Value<u32>)> {
+    match items(value)[..] {
+        [a, b] => Some((a, b)),
+        _ => None,
+    }
+}
+
+fn variant_name(value: &Value<u32>) -> Option<&str> {
+    match &value.value {
+        ValueDef::Variant(variant) => Some(&variant.name),
+        _ => None,
+    }
+}
+
+fn contains_variant(value: &Value<u32>, name: &str) -> bool {
+    match &value.value {
+        ValueDef::Variant(variant) => {
+            variant.name == name || variant.values.values().any(|v| contains_variant(v, name))
+        }
+        ValueDef::Composite(composite) => composite.values().any(|v| contains_variant(v, name)),
+        _ => false,
+    }
+}
+
+fn indent(text: &str) -> String {
+    text.lines().map(|line| format!("  {line}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+
+    // `DryRunApi.dry_run_xcm` results with no events or forwarded messages, for an XCM which
+    // used 1_000_000_000 ref time and 10_000 proof size:
+    // `Ok`, `Incomplete` or `Complete`, the weight, the `ExpectationFalse` error, and empty lists
+    const INCOMPLETE: &str = "000102286bee419c160000";
+    const COMPLETE: &str = "000002286bee419c0000";
+    // `Err(Unimplemented)`
+    const UNIMPLEMENTED: &str = "0100";
+
+    fn dry_run_xcm_result(network: Network, chain: ChainEnum, result: &str) -> Value<u32> {
+        let decoder = crate::decoder(network).unwrap();
+        let signature = signature(&decoder, chain, "dry_run_xcm").unwrap();
+        let data = hex::decode(result).unwrap();
+        let mut input = &data[..];
+        let value = decode_as_type(
+            &mut input,
+            signature.output,
+            decoder.metadata(chain).types(),
+        )
+        .unwrap();
+        assert!(input.is_empty(), "{network} {chain}: bytes left over");
+        value
+    }
+
+    #[test]
+    fn detects_failed_transact_status() {
+        // Polkadot and Paseo use XCM v4 outcomes, Westend XCM v5 outcomes
+        for network in [Network::Polkadot, Network::Westend, Network::Paseo] {
+            let decoder = crate::decoder(network).unwrap();
+            for chain in [ChainEnum::BridgeHub, ChainEnum::AssetHub] {
+                let result = dry_run_xcm_result(network, chain, INCOMPLETE);
+                let effects = report(&decoder, chain, &result).unwrap().unwrap();
+                let outcome = field(effects, "execution_result").unwrap();
+                assert_eq!(variant_name(outcome), Some("Incomplete"));
+                assert!(contains_variant(outcome, "ExpectationFalse"));
+                assert_eq!(xcm_succeeded(&decoder, chain, &result), Ok(false));
+            }
+        }
+    }
+
+    #[test]
+    fn accepts_complete_xcm() {
+        for network in [Network::Polkadot, Network::Westend, Network::Paseo] {
+            let decoder = crate::decoder(network).unwrap();
+            for chain in [ChainEnum::BridgeHub, ChainEnum::AssetHub] {
+                let result = dry_run_xcm_result(network, chain, COMPLETE);
+                assert_eq!(xcm_succeeded(&decoder, chain, &result), Ok(true));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_runtime_api_errors() {
+        let decoder = crate::decoder(Network::Polkadot).unwrap();
+        let result = dry_run_xcm_result(Network::Polkadot, ChainEnum::BridgeHub, UNIMPLEMENTED);
+        assert_eq!(
+            xcm_succeeded(&decoder, ChainEnum::BridgeHub, &result),
+            Ok(false)
+        );
+    }
+}
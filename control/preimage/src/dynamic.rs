@@ -0,0 +1,388 @@
+use alloy_primitives::U256;
+use codec::Decode;
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::Value as Json;
+use std::str::FromStr;
+use subxt::dynamic::Value;
+use subxt::ext::scale_value::{Composite, Primitive, ValueDef};
+use subxt::tx::Payload;
+use subxt::utils::AccountId32;
+use subxt::Metadata;
+
+/// Encode a call from its pallet and call names with arguments given as JSON, using the type
+/// information in the SCALE-encoded runtime metadata.
+///
+/// Arguments are a JSON object keyed by field name, or a JSON array of fields in order. Within
+/// them, enums are written as `"Variant"` or `{"Variant": fields}`, byte arrays and hashes as
+/// hex strings, account IDs as hex or SS58, and integers too large for JSON as decimal strings.
+pub fn encode_call(
+    metadata: &[u8],
+    pallet: &str,
+    call: &str,
+    args: &Json,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let metadata = Metadata::decode(&mut &metadata[..])?;
+    let pallet_metadata = metadata
+        .pallet_by_name(pallet)
+        .ok_or_else(|| format!("Pallet {pallet} not found in metadata"))?;
+    let variant = pallet_metadata
+        .call_variant_by_name(call)
+        .ok_or_else(|| format!("Call {pallet}.{call} not found in metadata"))?;
+
+    let fields = composite(metadata.types(), &variant.fields, args)
+        .map_err(|e| format!("Invalid arguments for {pallet}.{call}: {e}"))?;
+
+    Ok(subxt::dynamic::tx(pallet, call, fields).encode_call_data(&metadata)?)
+}
+
+fn composite(
+    types: &PortableRegistry,
+    fields: &[Field<PortableForm>],
+    json: &Json,
+) -> Result<Composite<()>, String> {
+    match json {
+        Json::Object(map) if fields.iter().all(|field| field.name.is_some()) => {
+            if let Some(key) = map
+                .keys()
+                .find(|key| !fields.iter().any(|field| field.name.as_ref() == Some(key)))
+            {
+                return Err(format!("unknown field `{key}`"));
+            }
+            let values = fields
+                .iter()
+                .map(|field| {
+                    let name = field.name.clone().unwrap_or_default();
+                    let json = map
+                        .get(&name)
+                        .ok_or_else(|| format!("missing field `{name}`"))?;
+                    let value =
+                        value(types, field.ty.id, json).map_err(|e| format!("{name}: {e}"))?;
+                    Ok((name, value))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(Composite::named(values))
+        }
+        // A single field may be given directly, as for newtypes, or wrapped in an array
+        json if fields.len() == 1 => {
+            let type_id = fields[0].ty.id;
+            match (value(types, type_id, json), json) {
+                (Ok(value), _) => Ok(Composite::unnamed([value])),
+                (Err(e), Json::Array(items)) if items.len() == 1 => {
+                    let value = value(types, type_id, &items[0]).map_err(|_| e)?;
+                    Ok(Composite::unnamed([value]))
+                }
+                (Err(e), _) => Err(e),
+            }
+        }
+        Json::Array(items) if items.len() == fields.len() => {
+            let values = fields
+                .iter()
+                .zip(items)
+                .map(|(field, json)| value(types, field.ty.id, json))
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(Composite::unnamed(values))
+        }
+        Json::Null if fields.is_empty() => Ok(Composite::unnamed([])),
+        _ => Err(format!("expected {} fields, got {json}", fields.len())),
+    }
+}
+
+fn value(types: &PortableRegistry, type_id: u32, json: &Json) -> Result<Value, String> {
+    let ty = types
+        .resolve(type_id)
+        .ok_or_else(|| format!("type {type_id} not found in metadata"))?;
+
+    match &ty.type_def {
+        TypeDef::Composite(def) => {
+            // 256-bit integers from primitive-types are stored as little-endian u64 limbs
+            if ty.path.ident().as_deref() == Some("U256") {
+                let Value {
+                    value: ValueDef::Primitive(Primitive::U256(bytes)),
+                    ..
+                } = primitive(&TypeDefPrimitive::U256, json)?
+                else {
+                    unreachable!()
+                };
+                let limbs = U256::from_le_bytes(bytes).into_limbs();
+                return Ok(Value::unnamed_composite([Value::unnamed_composite(
+                    limbs.map(|limb| Value::u128(limb as u128)),
+                )]));
+            }
+            if ty.path.ident().as_deref() == Some("AccountId32") {
+                if let Some(address) = json.as_str().filter(|s| !s.starts_with("0x")) {
+                    let account = AccountId32::from_str(address).map_err(|e| e.to_string())?;
+                    return Ok(Value::from_bytes(account.0));
+                }
+            }
+            Ok(Value::without_context(ValueDef::Composite(composite(
+                types,
+                &def.fields,
+                json,
+            )?)))
+        }
+        TypeDef::Variant(def) => {
+            let is_option = ty.path.ident().as_deref() == Some("Option");
+            let (name, fields) = match json {
+                // Options may be given as null or as the inner value directly
+                Json::Null if is_option => ("None", json),
+                Json::Object(map)
+                    if map.len() == 1
+                        && (!is_option || map.contains_key("Some") || map.contains_key("None")) =>
+                {
+                    let (name, fields) = map.iter().next().unwrap();
+                    (name.as_str(), fields)
+                }
+                _ if is_option => {
+                    let inner = ty.type_params[0].ty.ok_or("Option without a type")?;
+                    return Ok(Value::unnamed_variant(
+                        "Some",
+                        [value(types, inner.id, json)?],
+                    ));
+                }
+                Json::String(name) => (name.as_str(), &Json::Null),
+                _ => return Err(format!("expected a variant, got {json}")),
+            };
+            let variant = def
+                .variants
+                .iter()
+                .find(|variant| variant.name == name)
+                .ok_or_else(|| format!("unknown variant `{name}`"))?;
+            let fields =
+                composite(types, &variant.fields, fields).map_err(|e| format!("{name}: {e}"))?;
+            Ok(Value::variant(name, fields))
+        }
+        TypeDef::Sequence(def) => sequence(types, def.type_param.id, None, json),
+        TypeDef::Array(def) => sequence(types, def.type_param.id, Some(def.len as usize), json),
+        TypeDef::Tuple(def) => match json {
+            Json::Array(items) if items.len() == def.fields.len() => Ok(Value::unnamed_composite(
+                def.fields
+                    .iter()
+                    .zip(items)
+                    .map(|(field, json)| value(types, field.id, json))
+                    .collect::<Result<Vec<_>, String>>()?,
+            )),
+            _ => Err(format!(
+                "expected a tuple of {} values, got {json}",
+                def.fields.len()
+            )),
+        },
+        TypeDef::Compact(def) => value(types, def.type_param.id, json),
+        TypeDef::Primitive(def) => primitive(def, json),
+        TypeDef::BitSequence(_) => Err("bit sequences are not supported".into()),
+    }
+}
+
+fn sequence(
+    types: &PortableRegistry,
+    item_type_id: u32,
+    len: Option<usize>,
+    json: &Json,
+) -> Result<Value, String> {
+    let is_bytes = matches!(
+        types.resolve(item_type_id).map(|ty| &ty.type_def),
+        Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+    );
+    let values = match json {
+        Json::String(data) if is_bytes => hex::decode(data.trim_start_matches("0x"))
+            .map_err(|e| format!("invalid hex: {e}"))?
+            .into_iter()
+            .map(|byte| Value::u128(byte as u128))
+            .collect(),
+        Json::Array(items) => items
+            .iter()
+            .map(|json| value(types, item_type_id, json))
+            .collect::<Result<Vec<_>, String>>()?,
+        _ => return Err(format!("expected a sequence, got {json}")),
+    };
+    match len {
+        Some(len) if len != values.len() => {
+            Err(format!("expected {len} items, got {}", values.len()))
+        }
+        _ => Ok(Value::unnamed_composite(values)),
+    }
+}
+
+fn primitive(def: &TypeDefPrimitive, json: &Json) -> Result<Value, String> {
+    // Large integers don't fit in JSON numbers, so decimal strings are accepted too
+    let text = match json {
+        Json::String(text) => text.clone(),
+        Json::Number(number) => number.to_string(),
+        Json::Bool(value) => value.to_string(),
+        _ => return Err(format!("expected a primitive, got {json}")),
+    };
+    let invalid = |e: &dyn std::fmt::Display| format!("invalid {def:?} `{text}`: {e}");
+    match def {
+        TypeDefPrimitive::Bool => text.parse().map(Value::bool).map_err(|e| invalid(&e)),
+        TypeDefPrimitive::Char => text.parse().map(Value::char).map_err(|e| invalid(&e)),
+        TypeDefPrimitive::Str => Ok(Value::string(text)),
+        TypeDefPrimitive::U8
+        | TypeDefPrimitive::U16
+        | TypeDefPrimitive::U32
+        | TypeDefPrimitive::U64
+        | TypeDefPrimitive::U128 => text.parse().map(Value::u128).map_err(|e| invalid(&e)),
+        TypeDefPrimitive::I8
+        | TypeDefPrimitive::I16
+        | TypeDefPrimitive::I32
+        | TypeDefPrimitive::I64
+        | TypeDefPrimitive::I128 => text.parse().map(Value::i128).map_err(|e| invalid(&e)),
+        TypeDefPrimitive::U256 => U256::from_str(&text)
+            .map(|value| Value::primitive(Primitive::U256(value.to_le_bytes())))
+            .map_err(|e| invalid(&e)),
+        TypeDefPrimitive::I256 => Err("i256 is not supported".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::polkadot::{bridge_hub_runtime, relay_runtime};
+    use bridge_hub_runtime::runtime_types::{
+        primitive_types,
+        snowbridge_core::outbound::v1::OperatingMode,
+        snowbridge_core::pricing::{PricingParameters, Rewards},
+        snowbridge_pallet_system,
+    };
+    use codec::Encode;
+    use serde_json::json;
+    use sp_arithmetic::FixedU128;
+    use subxt::utils::Static;
+
+    // Encode a call on BridgeHub, checking that it decodes as the static `RuntimeCall` and
+    // encodes back to the same bytes
+    fn bridge_hub_call(call: &str, args: Json) -> Vec<u8> {
+        let encoded =
+            encode_call(bridge_hub_runtime::METADATA, "EthereumSystem", call, &args).unwrap();
+        let decoded = bridge_hub_runtime::RuntimeCall::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded.encode(), encoded);
+        encoded
+    }
+
+    #[test]
+    fn encodes_a_remark() {
+        let encoded = encode_call(
+            relay_runtime::METADATA,
+            "System",
+            "remark",
+            &json!({ "remark": "0x0102ff" }),
+        )
+        .unwrap();
+        let decoded = relay_runtime::RuntimeCall::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded.encode(), encoded);
+        let expected = relay_runtime::RuntimeCall::System(
+            relay_runtime::runtime_types::frame_system::pallet::Call::remark {
+                remark: vec![1, 2, 255],
+            },
+        );
+        assert_eq!(encoded, expected.encode());
+    }
+
+    #[test]
+    fn encodes_u256_fields() {
+        let remote_reward = U256::from(10).pow(U256::from(30));
+        let encoded = bridge_hub_call(
+            "set_pricing_parameters",
+            json!({
+                "params": {
+                    "exchange_rate": "400000000000000",
+                    "rewards": { "local": 2_000_000_000u64, "remote": remote_reward.to_string() },
+                    "fee_per_gas": 80_000_000_000u64,
+                    "multiplier": ["1330000000000000000"],
+                }
+            }),
+        );
+        let expected = bridge_hub_runtime::RuntimeCall::EthereumSystem(
+            snowbridge_pallet_system::pallet::Call::set_pricing_parameters {
+                params: PricingParameters {
+                    exchange_rate: Static(FixedU128::from_inner(400_000_000_000_000)),
+                    rewards: Rewards {
+                        local: 2_000_000_000,
+                        remote: primitive_types::U256(remote_reward.into_limbs()),
+                    },
+                    fee_per_gas: primitive_types::U256(U256::from(80_000_000_000u64).into_limbs()),
+                    multiplier: Static(FixedU128::from_inner(1_330_000_000_000_000_000)),
+                },
+            },
+        );
+        assert_eq!(encoded, expected.encode());
+    }
+
+    #[test]
+    fn encodes_enum_arguments() {
+        let expected = bridge_hub_runtime::RuntimeCall::EthereumSystem(
+            snowbridge_pallet_system::pallet::Call::set_operating_mode {
+                mode: OperatingMode::RejectingOutboundMessages,
+            },
+        )
+        .encode();
+        for args in [
+            json!({ "mode": "RejectingOutboundMessages" }),
+            json!({ "mode": { "RejectingOutboundMessages": null } }),
+            json!(["RejectingOutboundMessages"]),
+            json!("RejectingOutboundMessages"),
+        ] {
+            assert_eq!(bridge_hub_call("set_operating_mode", args), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let error = |pallet: &str, call: &str, args: Json| {
+            encode_call(bridge_hub_runtime::METADATA, pallet, call, &args)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("Snowbridge", "set_operating_mode", json!({})),
+            "Pallet Snowbridge not found in metadata"
+        );
+        assert_eq!(
+            error("EthereumSystem", "halt", json!({})),
+            "Call EthereumSystem.halt not found in metadata"
+        );
+        assert_eq!(
+            error("EthereumSystem", "set_operating_mode", json!({})),
+            "Invalid arguments for EthereumSystem.set_operating_mode: missing field `mode`"
+        );
+        assert_eq!(
+            error(
+                "EthereumSystem",
+                "set_operating_mode",
+                json!({ "mode": "Normal", "force": true })
+            ),
+            "Invalid arguments for EthereumSystem.set_operating_mode: unknown field `force`"
+        );
+        assert_eq!(
+            error("EthereumSystem", "set_operating_mode", json!({ "mode": "Halted" })),
+            "Invalid arguments for EthereumSystem.set_operating_mode: mode: unknown variant `Halted`"
+        );
+        assert_eq!(
+            error("EthereumSystem", "set_operating_mode", json!({ "mode": 1 })),
+            "Invalid arguments for EthereumSystem.set_operating_mode: mode: expected a variant, got 1"
+        );
+        assert_eq!(
+            error(
+                "EthereumSystem",
+                "set_token_transfer_fees",
+                json!({
+                    "create_asset_xcm": "1.5",
+                    "transfer_asset_xcm": 1,
+                    "register_token": 1,
+                })
+            ),
+            "Invalid arguments for EthereumSystem.set_token_transfer_fees: create_asset_xcm: \
+             invalid U128 `1.5`: invalid digit found in string"
+        );
+        assert_eq!(
+            error(
+                "System",
+                "remark",
+                json!({ "remark": "0xzz" })
+            ),
+            "Invalid arguments for System.remark: remark: invalid hex: Invalid character 'z' at position 0"
+        );
+        assert_eq!(
+            error("System", "remark", json!({ "remark": { "bytes": [] } })),
+            "Invalid arguments for System.remark: remark: expected a sequence, got {\"bytes\":[]}"
+        );
+    }
+}
@@ -0,0 +1,810 @@
+use alloy_dyn_abi::{DynSolType, DynSolValue, ResolveSolType};
+use alloy_json_abi::{JsonAbi, Param};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+/// Minimal JSON-RPC client for an Ethereum execution node, querying the latest block or the
+/// block it is pinned to
+pub struct EthereumRpc {
+    client: reqwest::Client,
+    url: String,
+    block: Option<u64>,
+}
+
+impl EthereumRpc {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            block: None,
+        }
+    }
+
+    /// Query the state at `block` instead of the latest block
+    pub fn at(self, block: u64) -> Self {
+        Self {
+            block: Some(block),
+            ..self
+        }
+    }
+
+    // Block parameter of the state queries
+    fn block_tag(&self) -> String {
+        self.block
+            .map_or("latest".into(), |block| format!("0x{block:x}"))
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, Box<dyn std::error::Error>> {
+        let response: RpcResponse = self
+            .client
+            .post(&self.url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.error {
+            return Err(format!("{method} failed: {} (code {})", error.message, error.code).into());
+        }
+        response
+            .result
+            .ok_or_else(|| format!("{method} returned no result").into())
+    }
+
+    pub async fn block_number(&self) -> Result<u64, Box<dyn std::error::Error>> {
+        let result = self.request("eth_blockNumber", json!([])).await?;
+        let number = result
+            .as_str()
+            .ok_or("eth_blockNumber: expected hex quantity")?;
+        Ok(u64::from_str_radix(number.trim_start_matches("0x"), 16)?)
+    }
+
+    pub async fn get_code(&self, address: Address) -> Result<Bytes, Box<dyn std::error::Error>> {
+        let result = self
+            .request(
+                "eth_getCode",
+                json!([address.to_string(), self.block_tag()]),
+            )
+            .await?;
+        let code = result.as_str().ok_or("eth_getCode: expected hex string")?;
+        Ok(hex::decode(code.trim_start_matches("0x"))?.into())
+    }
+
+    pub async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+    ) -> Result<B256, Box<dyn std::error::Error>> {
+        let result = self
+            .request(
+                "eth_getStorageAt",
+                json!([address.to_string(), format!("{slot:#x}"), self.block_tag()]),
+            )
+            .await?;
+        let value = result
+            .as_str()
+            .ok_or("eth_getStorageAt: expected hex string")?;
+        Ok(value.parse()?)
+    }
+
+    pub async fn call(
+        &self,
+        to: Address,
+        data: &[u8],
+    ) -> Result<Bytes, Box<dyn std::error::Error>> {
+        let result = self
+            .request(
+                "eth_call",
+                json!([
+                    { "to": to.to_string(), "data": format!("0x{}", hex::encode(data)) },
+                    self.block_tag()
+                ]),
+            )
+            .await?;
+        let data = result.as_str().ok_or("eth_call: expected hex string")?;
+        Ok(hex::decode(data.trim_start_matches("0x"))?.into())
+    }
+
+    /// Estimate gas for a call to `to`, optionally replacing the code deployed at `to`.
+    pub async fn estimate_gas(
+        &self,
+        to: Address,
+        data: &[u8],
+        code_override: Option<&[u8]>,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let mut params = vec![
+            json!({ "to": to.to_string(), "data": format!("0x{}", hex::encode(data)) }),
+            json!(self.block_tag()),
+        ];
+        if let Some(code) = code_override {
+            params.push(json!({ to.to_string(): { "code": format!("0x{}", hex::encode(code)) } }));
+        }
+        let result = self
+            .request("eth_estimateGas", Value::Array(params))
+            .await?;
+        let gas = result
+            .as_str()
+            .ok_or("eth_estimateGas: expected hex quantity")?;
+        Ok(u64::from_str_radix(gas.trim_start_matches("0x"), 16)?)
+    }
+
+    /// Gas prices of up to `count` blocks ending at `newest`, or at the queried block, from
+    /// `eth_feeHistory`. Each price is the base fee of a block plus the priority fee paid at
+    /// `percentile` of its gas used. Returns the number of the oldest block with the prices.
+    pub async fn fee_history(
+        &self,
+        count: u64,
+        newest: Option<u64>,
+        percentile: f64,
+    ) -> Result<(u64, Vec<u128>), Box<dyn std::error::Error>> {
+        let newest = newest
+            .or(self.block)
+            .map_or("latest".into(), |block| format!("0x{block:x}"));
+        let result = self
+            .request(
+                "eth_feeHistory",
+                json!([format!("0x{count:x}"), newest, [percentile]]),
+            )
+            .await?;
+        let quantity = |value: &Value| -> Result<u128, Box<dyn std::error::Error>> {
+            let value = value
+                .as_str()
+                .ok_or("eth_feeHistory: expected hex quantity")?;
+            Ok(u128::from_str_radix(value.trim_start_matches("0x"), 16)?)
+        };
+        let oldest_block = quantity(&result["oldestBlock"])?.try_into()?;
+        let base_fees = result["baseFeePerGas"]
+            .as_array()
+            .ok_or("eth_feeHistory: expected base fees")?;
+        let rewards = result["reward"]
+            .as_array()
+            .ok_or("eth_feeHistory: expected rewards")?;
+
+        // The base fees include the one of the block after the newest
+        let mut prices = vec![];
+        for (base_fee, reward) in base_fees.iter().zip(rewards) {
+            prices.push(quantity(base_fee)?.saturating_add(quantity(&reward[0])?));
+        }
+        Ok((oldest_block, prices))
+    }
+}
+
+/// Calldata for `IInitializable.initialize(bytes)`
+pub fn encode_initialize(params: &[u8]) -> Vec<u8> {
+    let mut data = keccak256("initialize(bytes)")[..4].to_vec();
+    data.extend(DynSolValue::Tuple(vec![DynSolValue::Bytes(params.to_vec())]).abi_encode_params());
+    data
+}
+
+/// Operating mode of the Gateway, `0` for `Normal` and `1` for `RejectingOutboundMessages`
+pub async fn gateway_operating_mode(
+    rpc: &EthereumRpc,
+    gateway: Address,
+) -> Result<u8, Box<dyn std::error::Error>> {
+    let data = rpc
+        .call(gateway, &keccak256("operatingMode()")[..4])
+        .await?;
+    let mode = U256::try_from_be_slice(&data).ok_or("operatingMode: invalid return data")?;
+    Ok(mode.try_into()?)
+}
+
+/// Token transfer fees set on the Gateway, as `(create_asset_xcm, transfer_asset_xcm,
+/// register_token)`. They are read from `AssetsStorage`, as the Gateway has no getters for them:
+/// `assetHubAgent` and `assetHubParaID` share the slot after the token registry, then the two
+/// XCM fees share the next one.
+pub async fn gateway_token_transfer_fees(
+    rpc: &EthereumRpc,
+    gateway: Address,
+) -> Result<(u128, u128, U256), Box<dyn std::error::Error>> {
+    let slot = U256::from_be_bytes(keccak256("org.snowbridge.storage.assets").0);
+    let xcm_fees = rpc.get_storage_at(gateway, slot + U256::from(2)).await?;
+    let register_token = rpc.get_storage_at(gateway, slot + U256::from(3)).await?;
+
+    let create_asset_xcm = u128::from_be_bytes(xcm_fees[16..].try_into()?);
+    let transfer_asset_xcm = u128::from_be_bytes(xcm_fees[..16].try_into()?);
+    Ok((
+        create_asset_xcm,
+        transfer_asset_xcm,
+        U256::from_be_bytes(register_token.0),
+    ))
+}
+
+/// Fee in Ether charged by the Gateway for registering a token, from `quoteRegisterTokenFee()`
+pub async fn gateway_quote_register_token_fee(
+    rpc: &EthereumRpc,
+    gateway: Address,
+) -> Result<U256, Box<dyn std::error::Error>> {
+    let data = rpc
+        .call(gateway, &keccak256("quoteRegisterTokenFee()")[..4])
+        .await?;
+    Ok(U256::try_from_be_slice(&data).ok_or("quoteRegisterTokenFee: invalid return data")?)
+}
+
+/// Fee in Ether charged by the Gateway for sending a registered token to a parachain, from
+/// `quoteSendTokenFee(address,uint32,uint128)`. The Gateway reverts for unregistered tokens.
+pub async fn gateway_quote_send_token_fee(
+    rpc: &EthereumRpc,
+    gateway: Address,
+    token: Address,
+    destination_chain: u32,
+    destination_fee: u128,
+) -> Result<U256, Box<dyn std::error::Error>> {
+    let mut data = keccak256("quoteSendTokenFee(address,uint32,uint128)")[..4].to_vec();
+    data.extend(
+        DynSolValue::Tuple(vec![
+            DynSolValue::Address(token),
+            DynSolValue::Uint(U256::from(destination_chain), 32),
+            DynSolValue::Uint(U256::from(destination_fee), 128),
+        ])
+        .abi_encode_params(),
+    );
+    let data = rpc.call(gateway, &data).await?;
+    Ok(U256::try_from_be_slice(&data).ok_or("quoteSendTokenFee: invalid return data")?)
+}
+
+/// Intrinsic gas charged for a transaction carrying `data`, which is not available to the callee
+pub fn intrinsic_gas(data: &[u8]) -> u64 {
+    data.iter()
+        .fold(21000, |gas, byte| gas + if *byte == 0 { 4 } else { 16 })
+}
+
+/// Parameter layout from a Solidity parameter list, e.g. `(uint8 mode, uint128 deliveryCost)`.
+/// Unlike canonical signatures, components of nested tuples may be named as well.
+pub fn parse_layout(signature: &str) -> Result<Vec<Param>, String> {
+    let mut input = signature.trim();
+    let layout = parse_param_list(&mut input)?;
+    if !input.is_empty() {
+        return Err(format!("unexpected `{input}`"));
+    }
+    Ok(layout)
+}
+
+fn parse_param_list(input: &mut &str) -> Result<Vec<Param>, String> {
+    *input = input
+        .strip_prefix('(')
+        .ok_or("expected a parameter list in parentheses")?
+        .trim_start();
+    let mut params = vec![];
+    if let Some(rest) = input.strip_prefix(')') {
+        *input = rest.trim_start();
+        return Ok(params);
+    }
+    loop {
+        params.push(parse_param(input)?);
+        let (separator, rest) = input.split_at(input.len().min(1));
+        *input = rest.trim_start();
+        match separator {
+            "," => continue,
+            ")" => return Ok(params),
+            _ => return Err("expected `,` or `)`".into()),
+        }
+    }
+}
+
+fn parse_param(input: &mut &str) -> Result<Param, String> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let (ty, components) = if input.starts_with('(') {
+        ("tuple".to_string(), parse_param_list(input)?)
+    } else {
+        let end = input.find(|c| !is_word(c)).unwrap_or(input.len());
+        let (ty, rest) = input.split_at(end);
+        *input = rest.trim_start();
+        (ty.to_string(), vec![])
+    };
+    if ty.is_empty() {
+        return Err("expected a type".into());
+    }
+
+    let mut ty = ty;
+    while let Some(rest) = input.strip_prefix('[') {
+        let end = rest.find(']').ok_or("expected `]`")?;
+        ty.push_str(&format!("[{}]", rest[..end].trim()));
+        *input = rest[end + 1..].trim_start();
+    }
+
+    let end = input.find(|c| !is_word(c)).unwrap_or(input.len());
+    let (name, rest) = input.split_at(end);
+    *input = rest.trim_start();
+
+    let param = Param {
+        ty,
+        name: name.to_string(),
+        components,
+        internal_type: None,
+    };
+    param.resolve().map_err(|e| e.to_string())?;
+    Ok(param)
+}
+
+/// Parameter layout from the inputs of a function in a contract ABI, either a plain ABI JSON
+/// array or a Foundry build artifact from `contracts/out`
+pub fn load_layout(path: &Path, function: &str) -> Result<Vec<Param>, Box<dyn std::error::Error>> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let mut value: Value =
+        serde_json::from_str(&data).map_err(|e| format!("Invalid ABI {}: {e}", path.display()))?;
+    if let Some(abi) = value.get_mut("abi") {
+        value = abi.take();
+    }
+    let abi: JsonAbi = serde_json::from_str(&value.to_string())
+        .map_err(|e| format!("Invalid ABI {}: {e}", path.display()))?;
+
+    match abi.function(function).map(Vec::as_slice) {
+        Some([function]) => Ok(function.inputs.clone()),
+        Some(_) => Err(format!("Function {function} is overloaded in {}", path.display()).into()),
+        None => Err(format!("Function {function} not found in {}", path.display()).into()),
+    }
+}
+
+/// ABI-encode a JSON object keyed by parameter name, or a JSON array of values in order
+pub fn encode_params(
+    layout: &[Param],
+    value: &Value,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let ty = DynSolType::Tuple(resolve_layout(layout)?);
+    Ok(coerce(&ty, layout, value)?.abi_encode_params())
+}
+
+/// ABI-decode parameters, rejecting data which does not re-encode to the same bytes
+pub fn decode_params(
+    layout: &[Param],
+    data: &[u8],
+) -> Result<DynSolValue, Box<dyn std::error::Error>> {
+    let ty = DynSolType::Tuple(resolve_layout(layout)?);
+    let value = ty.abi_decode_params(data)?;
+    if value.abi_encode_params() != data {
+        return Err(format!("Data is not a canonical encoding of {}", ty).into());
+    }
+    Ok(value)
+}
+
+fn resolve_layout(layout: &[Param]) -> Result<Vec<DynSolType>, Box<dyn std::error::Error>> {
+    Ok(layout
+        .iter()
+        .map(|param| param.resolve())
+        .collect::<Result<_, _>>()?)
+}
+
+fn coerce(ty: &DynSolType, components: &[Param], value: &Value) -> Result<DynSolValue, String> {
+    match (ty, value) {
+        (DynSolType::Tuple(types), Value::Object(fields)) => {
+            if let Some(name) = fields
+                .keys()
+                .find(|name| !components.iter().any(|c| &c.name == *name))
+            {
+                return Err(format!("Unknown field `{name}`"));
+            }
+            types
+                .iter()
+                .zip(components)
+                .map(|(ty, component)| {
+                    let value = fields
+                        .get(&component.name)
+                        .ok_or_else(|| format!("Missing field `{}`", component.name))?;
+                    coerce(ty, &component.components, value)
+                        .map_err(|e| format!("{}: {e}", component.name))
+                })
+                .collect::<Result<_, _>>()
+                .map(DynSolValue::Tuple)
+        }
+        (DynSolType::Tuple(types), Value::Array(values)) if types.len() == values.len() => types
+            .iter()
+            .zip(values)
+            .enumerate()
+            .map(|(i, (ty, value))| {
+                let components = components
+                    .get(i)
+                    .map(|c| c.components.as_slice())
+                    .unwrap_or_default();
+                coerce(ty, components, value)
+            })
+            .collect::<Result<_, _>>()
+            .map(DynSolValue::Tuple),
+        (DynSolType::Array(ty), Value::Array(values)) => values
+            .iter()
+            .map(|value| coerce(ty, components, value))
+            .collect::<Result<_, _>>()
+            .map(DynSolValue::Array),
+        (DynSolType::FixedArray(ty, size), Value::Array(values)) if *size == values.len() => values
+            .iter()
+            .map(|value| coerce(ty, components, value))
+            .collect::<Result<_, _>>()
+            .map(DynSolValue::FixedArray),
+        (_, Value::String(s)) => ty.coerce_str(s).map_err(|e| e.to_string()),
+        (_, Value::Number(_) | Value::Bool(_)) => {
+            ty.coerce_str(&value.to_string()).map_err(|e| e.to_string())
+        }
+        _ => Err(format!("Cannot encode {value} as {ty}")),
+    }
+}
+
+/// Format decoded parameters as an indented tree, one value per line
+pub fn format_params(layout: &[Param], value: &DynSolValue, indent: usize) -> String {
+    let mut out = String::new();
+    write_params(&mut out, layout, value, indent);
+    out
+}
+
+fn write_params(out: &mut String, layout: &[Param], value: &DynSolValue, indent: usize) {
+    let DynSolValue::Tuple(values) = value else {
+        return;
+    };
+    for (i, (param, value)) in layout.iter().zip(values).enumerate() {
+        let name = if param.name.is_empty() {
+            i.to_string()
+        } else {
+            param.name.clone()
+        };
+        write_value(out, &name, &param.components, value, indent);
+    }
+}
+
+fn write_value(
+    out: &mut String,
+    name: &str,
+    components: &[Param],
+    value: &DynSolValue,
+    indent: usize,
+) {
+    let pad = " ".repeat(indent);
+    let line = match value {
+        DynSolValue::Tuple(_) => {
+            out.push_str(&format!("{pad}{name}:\n"));
+            write_params(out, components, value, indent + 2);
+            return;
+        }
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            out.push_str(&format!("{pad}{name}: [{}]\n", values.len()));
+            for (i, value) in values.iter().enumerate() {
+                write_value(out, &i.to_string(), components, value, indent + 2);
+            }
+            return;
+        }
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(value, size) => format!("0x{}", hex::encode(&value[..*size])),
+        DynSolValue::Bytes(value) => format!("0x{}", hex::encode(value)),
+        DynSolValue::String(value) => format!("{value:?}"),
+        _ => format!("{value:?}"),
+    };
+    out.push_str(&format!("{pad}{name}: {line}\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{self, Response};
+    use std::sync::{Arc, Mutex};
+
+    // Serve `result` for every JSON-RPC request, recording the requests
+    async fn ethereum_node(result: Value) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        let url = stub::serve(move |request| {
+            let request = request.json();
+            let id = request["id"].clone();
+            recorded.lock().unwrap().push(request);
+            Response::json(&json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+        })
+        .await;
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn estimates_gas_with_code_override() {
+        let (url, requests) = ethereum_node(json!("0x1d4c0")).await;
+        let gateway = Address::repeat_byte(0x11);
+        let data = encode_initialize(&[0xab, 0xcd]);
+
+        let gas = EthereumRpc::new(&url)
+            .estimate_gas(gateway, &data, Some(&[0x60, 0x80]))
+            .await
+            .unwrap();
+
+        assert_eq!(gas, 120_000);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["method"], "eth_estimateGas");
+        assert_eq!(
+            requests[0]["params"],
+            json!([
+                { "to": gateway.to_string(), "data": format!("0x{}", hex::encode(&data)) },
+                "latest",
+                { gateway.to_string(): { "code": "0x6080" } }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn estimates_gas_without_code_override() {
+        let (url, requests) = ethereum_node(json!("0x5208")).await;
+
+        let gas = EthereumRpc::new(&url)
+            .estimate_gas(Address::ZERO, &[], None)
+            .await
+            .unwrap();
+
+        assert_eq!(gas, 21_000);
+        let params = &requests.lock().unwrap()[0]["params"];
+        assert_eq!(params.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn queries_the_pinned_block() {
+        let (url, requests) = ethereum_node(json!(B256::ZERO)).await;
+        let rpc = EthereumRpc::new(&url).at(0x1234);
+
+        rpc.get_code(Address::ZERO).await.unwrap();
+        rpc.get_storage_at(Address::ZERO, U256::from(1))
+            .await
+            .unwrap();
+        rpc.call(Address::ZERO, &[]).await.unwrap();
+        rpc.estimate_gas(Address::ZERO, &[], None).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        for request in requests.iter() {
+            let params = request["params"].as_array().unwrap();
+            assert_eq!(params.last().unwrap(), "0x1234", "{}", request["method"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_rpc_errors() {
+        let url = stub::serve(|_| {
+            Response::json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": 3, "message": "execution reverted" }
+            }))
+        })
+        .await;
+
+        let error = EthereumRpc::new(&url)
+            .estimate_gas(Address::ZERO, &[], None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "eth_estimateGas failed: execution reverted (code 3)"
+        );
+    }
+
+    #[test]
+    fn encodes_initialize() {
+        let data = encode_initialize(&[0xab, 0xcd]);
+        assert_eq!(&data[..4], &keccak256("initialize(bytes)")[..4]);
+        // Offset, length and the padded params
+        assert_eq!(data.len(), 4 + 3 * 32);
+        assert_eq!(U256::from_be_slice(&data[4..36]), U256::from(32));
+        assert_eq!(U256::from_be_slice(&data[36..68]), U256::from(2));
+        assert_eq!(&data[68..70], &[0xab, 0xcd]);
+    }
+
+    #[test]
+    fn charges_intrinsic_gas_by_byte() {
+        assert_eq!(intrinsic_gas(&[]), 21_000);
+        assert_eq!(intrinsic_gas(&[0, 0, 1]), 21_000 + 2 * 4 + 16);
+    }
+
+    // Fields of `Initializer.Config` in contracts/src/Initializer.sol
+    const CONFIG: &str = "(uint8 mode, uint128 deliveryCost, uint256 exchangeRate, \
+        uint128 assetHubCreateAssetFee, uint128 assetHubReserveTransferFee, \
+        uint256 registerTokenFee, uint256 multiplier, uint8 foreignTokenDecimals, \
+        uint128 maxDestinationFee)";
+
+    fn config() -> Value {
+        json!({
+            "mode": 0,
+            "deliveryCost": "150000000000",
+            "exchangeRate": "2500000000000000",
+            "assetHubCreateAssetFee": 100_000_000_000u64,
+            "assetHubReserveTransferFee": 100_000_000_000u64,
+            "registerTokenFee": "0",
+            "multiplier": "1330000000000000000",
+            "foreignTokenDecimals": 10,
+            "maxDestinationFee": "2000000000000",
+        })
+    }
+
+    // `abi.encode(config)`: the fields are static, so each takes one word
+    fn encoded_config() -> Vec<u8> {
+        [
+            0u128,
+            150_000_000_000,
+            2_500_000_000_000_000,
+            100_000_000_000,
+            100_000_000_000,
+            0,
+            1_330_000_000_000_000_000,
+            10,
+            2_000_000_000_000,
+        ]
+        .iter()
+        .flat_map(|value| U256::from(*value).to_be_bytes::<32>())
+        .collect()
+    }
+
+    // A Foundry build artifact with a function taking the config as a struct
+    fn artifact() -> std::path::PathBuf {
+        let components: Vec<Value> = parse_layout(CONFIG)
+            .unwrap()
+            .iter()
+            .map(|param| json!({ "name": param.name, "type": param.ty, "internalType": param.ty }))
+            .collect();
+        let artifact = json!({
+            "abi": [{
+                "type": "function",
+                "name": "encodeConfig",
+                "inputs": [{
+                    "name": "config",
+                    "type": "tuple",
+                    "internalType": "struct Initializer.Config",
+                    "components": components,
+                }],
+                "outputs": [{ "name": "", "type": "bytes", "internalType": "bytes" }],
+                "stateMutability": "pure",
+            }],
+            "bytecode": { "object": "0x" },
+        });
+        let path = std::env::temp_dir().join(format!(
+            "snowbridge-preimage-{}-Initializer.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, artifact.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn encodes_params_from_a_signature() {
+        let layout = parse_layout(CONFIG).unwrap();
+        let data = encode_params(&layout, &config()).unwrap();
+        assert_eq!(data, encoded_config());
+
+        // Values may also be given in order
+        let values: Vec<Value> = layout
+            .iter()
+            .map(|param| config()[&param.name].clone())
+            .collect();
+        assert_eq!(encode_params(&layout, &Value::Array(values)).unwrap(), data);
+    }
+
+    #[test]
+    fn encodes_params_from_an_abi() {
+        let layout = load_layout(&artifact(), "encodeConfig").unwrap();
+        assert_eq!(layout.len(), 1);
+        assert_eq!(layout[0].name, "config");
+        let data = encode_params(&layout, &json!({ "config": config() })).unwrap();
+        assert_eq!(data, encoded_config());
+
+        let error = load_layout(&artifact(), "initialize").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Function initialize not found in "));
+    }
+
+    #[test]
+    fn decodes_and_prints_params() {
+        let layout = load_layout(&artifact(), "encodeConfig").unwrap();
+        let decoded = decode_params(&layout, &encoded_config()).unwrap();
+        assert_eq!(decoded.abi_encode_params(), encoded_config());
+        assert_eq!(
+            format_params(&layout, &decoded, 2),
+            "  config:
+    mode: 0
+    deliveryCost: 150000000000
+    exchangeRate: 2500000000000000
+    assetHubCreateAssetFee: 100000000000
+    assetHubReserveTransferFee: 100000000000
+    registerTokenFee: 0
+    multiplier: 1330000000000000000
+    foreignTokenDecimals: 10
+    maxDestinationFee: 2000000000000
+"
+        );
+
+        // Bytes which are too short for the layout
+        assert!(decode_params(&layout, &encoded_config()[..32]).is_err());
+    }
+
+    #[test]
+    fn parses_nested_layouts() {
+        let layout =
+            parse_layout(" ( (address token, uint128[2] fees) [] transfers , bytes ) ").unwrap();
+        assert_eq!(layout.len(), 2);
+        assert_eq!(layout[0].name, "transfers");
+        assert_eq!(layout[0].ty, "tuple[]");
+        assert_eq!(layout[0].components[0].name, "token");
+        assert_eq!(layout[0].components[1].ty, "uint128[2]");
+        assert_eq!(layout[1].name, "");
+
+        let value = json!([
+            [{ "token": "0x0000000000000000000000000000000000000001", "fees": [1, "2"] }],
+            "0xabcd"
+        ]);
+        let data = encode_params(&layout, &value).unwrap();
+        let decoded = decode_params(&layout, &data).unwrap();
+        assert_eq!(
+            format_params(&layout, &decoded, 0),
+            "transfers: [1]
+  0:
+    token: 0x0000000000000000000000000000000000000001
+    fees: [2]
+      0: 1
+      1: 2
+1: 0xabcd
+"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_layouts() {
+        assert_eq!(
+            parse_layout("uint8 mode").unwrap_err(),
+            "expected a parameter list in parentheses"
+        );
+        assert_eq!(
+            parse_layout("(uint8 mode").unwrap_err(),
+            "expected `,` or `)`"
+        );
+        assert_eq!(parse_layout("(uint8) x").unwrap_err(), "unexpected `x`");
+        assert!(parse_layout("(uint7 mode)").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        let layout = parse_layout(CONFIG).unwrap();
+        let error = |value: Value| encode_params(&layout, &value).unwrap_err().to_string();
+
+        let mut value = config();
+        value["gas"] = json!(1);
+        assert_eq!(error(value), "Unknown field `gas`");
+
+        let mut value = config();
+        value.as_object_mut().unwrap().remove("multiplier");
+        assert_eq!(error(value), "Missing field `multiplier`");
+
+        let mut value = config();
+        value["mode"] = json!([0]);
+        assert_eq!(error(value), "mode: Cannot encode [0] as uint8");
+
+        let mut value = config();
+        value["foreignTokenDecimals"] = json!(256);
+        assert!(error(value).starts_with("foreignTokenDecimals: "));
+
+        assert_eq!(
+            error(json!([0, 1])),
+            format!("Cannot encode [0,1] as {}", layout_type(&layout))
+        );
+    }
+
+    fn layout_type(layout: &[Param]) -> DynSolType {
+        DynSolType::Tuple(resolve_layout(layout).unwrap())
+    }
+}
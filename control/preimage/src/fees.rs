@@ -0,0 +1,152 @@
+use super::assets::print_table;
+use super::bridge_hub_runtime::runtime_types::snowbridge_core::{
+    outbound::v1::{AgentExecuteCommand, Command as OutboundCommand},
+    outbound::Fee,
+    pricing::PricingParameters,
+};
+use super::helpers::{ether, native};
+use super::queries::{calculate_fee, query_gateway_address, query_pricing_parameters};
+use super::snowbridge::build_pricing_parameters;
+use super::NETWORK;
+use crate::ethereum;
+use crate::{Context, QuoteFeesArgs};
+use alloy_primitives::{Address, U256};
+use subxt::utils::{H160, H256};
+
+// Messages sent to Ethereum by the flows of the bridge. Their fees depend on the gas the Gateway
+// spends on each kind of message rather than on its arguments, so the arguments are left empty.
+fn outbound_commands() -> Vec<(&'static str, OutboundCommand)> {
+    let mut commands = vec![(
+        "Transfer token",
+        OutboundCommand::AgentExecute {
+            agent_id: H256::zero(),
+            command: AgentExecuteCommand::TransferToken {
+                token: H160::zero(),
+                recipient: H160::zero(),
+                amount: 0,
+            },
+        },
+    )];
+    commands.extend(super::native_assets::outbound_commands());
+    commands.extend([
+        (
+            "Upgrade",
+            OutboundCommand::Upgrade {
+                impl_address: H160::zero(),
+                impl_code_hash: H256::zero(),
+                initializer: None,
+            },
+        ),
+        (
+            "Create agent",
+            OutboundCommand::CreateAgent {
+                agent_id: H256::zero(),
+            },
+        ),
+    ]);
+    commands
+}
+
+// Print the fees of the messages sent to Ethereum with `OutboundQueueApi.calculate_fee`, with the
+// current pricing parameters and the proposed ones if given. With `--ethereum-rpc`, also print
+// the fees charged by the Gateway for messages to Polkadot.
+pub async fn quote_fees(
+    context: &Context,
+    params: &QuoteFeesArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let proposed = params
+        .proposed
+        .as_ref()
+        .map(build_pricing_parameters)
+        .transpose()?;
+    print_outbound_fees(context, proposed.as_ref()).await?;
+
+    // The Gateway charges for messages to Polkadot with the pricing parameters last sent to it,
+    // so proposed parameters only apply once the proposal is enacted
+    let Some(ethereum_rpc) = &params.ethereum_rpc else {
+        return Ok(());
+    };
+    let gateway = query_gateway_address(&context.bridge_hub_api, context.bridge_hub_block.hash)
+        .await?
+        .map(|address| Address::from(address.0))
+        .ok_or("The gateway address is not set on BridgeHub")?;
+    let rpc = context.ethereum(ethereum_rpc).await?;
+
+    let register_token = ethereum::gateway_quote_register_token_fee(&rpc, gateway).await?;
+    let mut rows = vec![vec!["Register token".into(), ether(register_token)]];
+    if let Some(token) = params.token {
+        // The destination fee only applies to parachains beyond AssetHub
+        let send_token =
+            ethereum::gateway_quote_send_token_fee(&rpc, gateway, token, NETWORK.asset_hub_id(), 0)
+                .await?;
+        rows.push(vec![
+            format!("Transfer token {}", token.to_checksum(None)),
+            ether(send_token),
+        ]);
+    }
+    println!(
+        "Messages to Polkadot (Gateway {}):",
+        gateway.to_checksum(None)
+    );
+    print_table(&["MESSAGE", "FEE (ETH)"], rows);
+
+    Ok(())
+}
+
+// Print the fees of the messages sent to Ethereum with the current pricing parameters, and with
+// the proposed ones if given
+pub async fn print_outbound_fees(
+    context: &Context,
+    proposed: Option<&PricingParameters<u128>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let api = &context.bridge_hub_api;
+    let at = context.bridge_hub_block.hash;
+    let current = query_pricing_parameters(api, at).await?;
+
+    let mut rows = vec![];
+    for (message, command) in outbound_commands() {
+        let fee = calculate_fee(api, at, command.clone(), None).await?;
+        rows.push(fee_row(message, "current", &current, &fee));
+        if let Some(proposed) = proposed {
+            let fee = calculate_fee(api, at, command, Some(proposed)).await?;
+            rows.push(fee_row(message, "proposed", proposed, &fee));
+        }
+    }
+    println!(
+        "Messages to Ethereum (BridgeHub #{} {:?}):",
+        context.bridge_hub_block.number, context.bridge_hub_block.hash
+    );
+    print_table(
+        &[
+            "MESSAGE",
+            "PRICING",
+            "LOCAL",
+            "REMOTE",
+            "REMOTE (ETH)",
+            "TOTAL",
+        ],
+        rows,
+    );
+
+    Ok(())
+}
+
+// The remote fee is paid in the native token, and converted back to Ether at the exchange rate
+// of the pricing parameters it was quoted with
+fn fee_row(
+    message: &str,
+    pricing: &str,
+    params: &PricingParameters<u128>,
+    fee: &Fee<u128>,
+) -> Vec<String> {
+    let remote_ether = U256::from(fee.remote) * U256::from(params.exchange_rate.0.into_inner())
+        / U256::from(10).pow(U256::from(NETWORK.decimals()));
+    vec![
+        message.into(),
+        pricing.into(),
+        native(fee.local),
+        native(fee.remote),
+        ether(remote_ether),
+        native(fee.local.saturating_add(fee.remote)),
+    ]
+}
@@ -1,14 +1,15 @@
-use bridge_hub_runtime::ethereum_system::storage::types::pricing_parameters::PricingParameters;
-use codec::Encode;
-use subxt::{utils::H160, utils::H256, OnlineClient, PolkadotConfig};
+use codec::{Decode, Encode};
+use sp_crypto_hashing::blake2_256;
+use std::path::Path;
 
-use crate::constants::{ASSET_HUB_ID, BRIDGE_HUB_ID};
-use crate::Context;
+use super::NETWORK;
+use crate::provenance::{self, QueriedWeight};
+use crate::{ChainEnum, Context};
 
-use crate::bridge_hub_runtime::{self, RuntimeCall as BridgeHubRuntimeCall};
+use super::bridge_hub_runtime::{self, RuntimeCall as BridgeHubRuntimeCall};
 
-use crate::relay_runtime::runtime_types::{
-    pallet_xcm,
+use super::relay_runtime::runtime_types::{
+    pallet_scheduler, pallet_xcm,
     sp_weights::weight_v2::Weight,
     staging_xcm::v4::{
         junction::Junction,
@@ -22,11 +23,14 @@ use crate::relay_runtime::runtime_types::{
     xcm::{VersionedLocation, VersionedXcm},
 };
 
-use crate::asset_hub_runtime::RuntimeCall as AssetHubRuntimeCall;
-use crate::relay_runtime::RuntimeCall as RelayRuntimeCall;
+use super::asset_hub_runtime::RuntimeCall as AssetHubRuntimeCall;
+use super::relay_runtime::RuntimeCall as RelayRuntimeCall;
 
+use alloy_primitives::U256;
+use snowbridge_preimage_chopsticks::Schedule;
 use sp_arithmetic::helpers_128bit::multiply_by_rational_with_rounding;
 use sp_arithmetic::per_things::Rounding;
+use sp_arithmetic::FixedU128;
 
 const MAX_REF_TIME: u128 = 500_000_000_000 - 1;
 const MAX_PROOF_SIZE: u128 = 3 * 1024 * 1024 - 1;
@@ -45,6 +49,20 @@ pub fn increase_weight(ref_time: &mut u64, proof_size: &mut u64) {
     *proof_size = _proof_size.try_into().expect("overflow");
 }
 
+// Increase the queried weight of a call as a buffer, recording both for the manifest
+fn required_weight(chain: ChainEnum, call: &[u8], queried: QueriedWeight) -> (u64, u64) {
+    let (mut ref_time, mut proof_size) = (queried.ref_time, queried.proof_size);
+    increase_weight(&mut ref_time, &mut proof_size);
+    provenance::record_weight(provenance::Weight {
+        chain,
+        call_hash: blake2_256(call).into(),
+        queried,
+        ref_time,
+        proof_size,
+    });
+    (ref_time, proof_size)
+}
+
 pub async fn send_xcm_bridge_hub(
     context: &Context,
     calls: Vec<BridgeHubRuntimeCall>,
@@ -52,9 +70,8 @@ pub async fn send_xcm_bridge_hub(
     let mut accum: Vec<(u64, u64, Vec<u8>)> = vec![];
 
     for call in calls.iter() {
-        let (mut ref_time, mut proof_size) =
-            query_weight_bridge_hub(&context.bridge_hub_api, call.clone()).await?;
-        increase_weight(&mut ref_time, &mut proof_size);
+        let queried = query_weight_bridge_hub(context, call.clone()).await?;
+        let (ref_time, proof_size) = required_weight(ChainEnum::BridgeHub, &call.encode(), queried);
         accum.push((ref_time, proof_size, call.encode()));
     }
 
@@ -68,8 +85,8 @@ pub async fn send_xcm_bridge_hub(
             Transact {
                 origin_kind: OriginKind::Superuser,
                 require_weight_at_most: Weight {
-                    ref_time: ref_time,
-                    proof_size: proof_size,
+                    ref_time,
+                    proof_size,
                 },
                 call: DoubleEncoded { encoded },
             },
@@ -80,7 +97,7 @@ pub async fn send_xcm_bridge_hub(
     let call = RelayRuntimeCall::XcmPallet(pallet_xcm::pallet::Call::send {
         dest: Box::new(VersionedLocation::V4(Location {
             parents: 0,
-            interior: Junctions::X1([Junction::Parachain(BRIDGE_HUB_ID)]),
+            interior: Junctions::X1([Junction::Parachain(NETWORK.bridge_hub_id())]),
         })),
         message: Box::new(VersionedXcm::V4(Xcm(instructions))),
     });
@@ -95,9 +112,8 @@ pub async fn send_xcm_asset_hub(
     let mut accum: Vec<(u64, u64, Vec<u8>)> = vec![];
 
     for call in calls.iter() {
-        let (mut ref_time, mut proof_size) =
-            query_weight_asset_hub(&context.asset_hub_api, call.clone()).await?;
-        increase_weight(&mut ref_time, &mut proof_size);
+        let queried = query_weight_asset_hub(context, call.clone()).await?;
+        let (ref_time, proof_size) = required_weight(ChainEnum::AssetHub, &call.encode(), queried);
         accum.push((ref_time, proof_size, call.encode()));
     }
 
@@ -111,8 +127,8 @@ pub async fn send_xcm_asset_hub(
             Transact {
                 origin_kind: OriginKind::Superuser,
                 require_weight_at_most: Weight {
-                    ref_time: ref_time,
-                    proof_size: proof_size,
+                    ref_time,
+                    proof_size,
                 },
                 call: DoubleEncoded { encoded },
             },
@@ -123,7 +139,7 @@ pub async fn send_xcm_asset_hub(
     let call = RelayRuntimeCall::XcmPallet(pallet_xcm::pallet::Call::send {
         dest: Box::new(VersionedLocation::V4(Location {
             parents: 0,
-            interior: Junctions::X1([Junction::Parachain(ASSET_HUB_ID)]),
+            interior: Junctions::X1([Junction::Parachain(NETWORK.asset_hub_id())]),
         })),
         message: Box::new(VersionedXcm::V4(Xcm(instructions))),
     });
@@ -131,101 +147,356 @@ pub async fn send_xcm_asset_hub(
     Ok(call)
 }
 
+// Calls to dispatch on one chain
+pub enum ChainCalls {
+    BridgeHub(Vec<BridgeHubRuntimeCall>),
+    AssetHub(Vec<AssetHubRuntimeCall>),
+    Relay(Vec<RelayRuntimeCall>),
+}
+
+// Calls to dispatch across the chains in the order they were added, grouped into runs of
+// consecutive calls on the same chain
+#[derive(Default)]
+pub struct Calls(Vec<ChainCalls>);
+
+impl Calls {
+    pub fn bridge_hub(calls: Vec<BridgeHubRuntimeCall>) -> Self {
+        let mut this = Self::default();
+        this.push(ChainCalls::BridgeHub(calls));
+        this
+    }
+
+    pub fn asset_hub(calls: Vec<AssetHubRuntimeCall>) -> Self {
+        let mut this = Self::default();
+        this.push(ChainCalls::AssetHub(calls));
+        this
+    }
+
+    pub fn relay(calls: Vec<RelayRuntimeCall>) -> Self {
+        let mut this = Self::default();
+        this.push(ChainCalls::Relay(calls));
+        this
+    }
+
+    pub fn push_bridge_hub(&mut self, call: BridgeHubRuntimeCall) {
+        self.push(ChainCalls::BridgeHub(vec![call]));
+    }
+
+    pub fn push_asset_hub(&mut self, call: AssetHubRuntimeCall) {
+        self.push(ChainCalls::AssetHub(vec![call]));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn extend(&mut self, other: Calls) {
+        for calls in other.0 {
+            self.push(calls);
+        }
+    }
+
+    // Append calls to the last run if it is on the same chain, otherwise start a new run
+    fn push(&mut self, calls: ChainCalls) {
+        match (self.0.last_mut(), calls) {
+            (_, ChainCalls::BridgeHub(calls)) if calls.is_empty() => {}
+            (_, ChainCalls::AssetHub(calls)) if calls.is_empty() => {}
+            (_, ChainCalls::Relay(calls)) if calls.is_empty() => {}
+            (Some(ChainCalls::BridgeHub(last)), ChainCalls::BridgeHub(calls)) => last.extend(calls),
+            (Some(ChainCalls::AssetHub(last)), ChainCalls::AssetHub(calls)) => last.extend(calls),
+            (Some(ChainCalls::Relay(last)), ChainCalls::Relay(calls)) => last.extend(calls),
+            (_, calls) => self.0.push(calls),
+        }
+    }
+}
+
+// Send each run of BridgeHub or AssetHub calls via XCM, keeping the order of the calls across
+// the chains, and batch them if there is more than one relay chain call.
+pub async fn send_calls(
+    context: &Context,
+    calls: Calls,
+) -> Result<RelayRuntimeCall, Box<dyn std::error::Error>> {
+    if calls.is_empty() {
+        return Err("No calls to dispatch".into());
+    }
+    let mut relay_calls = vec![];
+    for calls in calls.0 {
+        match calls {
+            ChainCalls::BridgeHub(calls) => {
+                relay_calls.push(send_xcm_bridge_hub(context, calls).await?)
+            }
+            ChainCalls::AssetHub(calls) => {
+                relay_calls.push(send_xcm_asset_hub(context, calls).await?)
+            }
+            ChainCalls::Relay(calls) => relay_calls.extend(calls),
+        }
+    }
+
+    match relay_calls.len() {
+        1 => Ok(relay_calls.remove(0)),
+        _ => Ok(utility_force_batch(relay_calls)),
+    }
+}
+
+// XCM which the Fellowship sends from the Collectives chain, dispatching the call on the relay
+// chain with the Fellowship's XCM origin
+pub async fn fellowship_xcm(
+    context: &Context,
+    call: RelayRuntimeCall,
+) -> Result<VersionedXcm, Box<dyn std::error::Error>> {
+    let queried = query_weight_relay(context, call.clone()).await?;
+    let (ref_time, proof_size) = required_weight(ChainEnum::Relay, &call.encode(), queried);
+
+    Ok(VersionedXcm::V4(Xcm(vec![
+        UnpaidExecution {
+            weight_limit: WeightLimit::Unlimited,
+            check_origin: None,
+        },
+        Transact {
+            origin_kind: OriginKind::Xcm,
+            require_weight_at_most: Weight {
+                ref_time,
+                proof_size,
+            },
+            call: DoubleEncoded {
+                encoded: call.encode(),
+            },
+        },
+    ])))
+}
+
+pub async fn query_weight_relay(
+    context: &Context,
+    call: RelayRuntimeCall,
+) -> Result<QueriedWeight, Box<dyn std::error::Error>> {
+    let runtime_api_call = super::relay_runtime::apis()
+        .transaction_payment_call_api()
+        .query_call_info(call, 0);
+    let block = context.relay_block;
+    let call_info = context
+        .relay_api
+        .runtime_api()
+        .at(block.hash)
+        .call(runtime_api_call)
+        .await?;
+    Ok(QueriedWeight {
+        ref_time: call_info.weight.ref_time,
+        proof_size: call_info.weight.proof_size,
+        block_number: block.number,
+        block_hash: block.hash,
+    })
+}
+
 pub async fn query_weight_bridge_hub(
-    api: &OnlineClient<PolkadotConfig>,
+    context: &Context,
     call: BridgeHubRuntimeCall,
-) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+) -> Result<QueriedWeight, Box<dyn std::error::Error>> {
     let runtime_api_call = bridge_hub_runtime::apis()
         .transaction_payment_call_api()
         .query_call_info(call, 0);
-    let call_info = api
+    let block = context.bridge_hub_block;
+    let call_info = context
+        .bridge_hub_api
         .runtime_api()
-        .at_latest()
-        .await?
+        .at(block.hash)
         .call(runtime_api_call)
         .await?;
-    Ok((call_info.weight.ref_time, call_info.weight.proof_size))
+    Ok(QueriedWeight {
+        ref_time: call_info.weight.ref_time,
+        proof_size: call_info.weight.proof_size,
+        block_number: block.number,
+        block_hash: block.hash,
+    })
 }
 
 pub async fn query_weight_asset_hub(
-    api: &OnlineClient<PolkadotConfig>,
+    context: &Context,
     call: AssetHubRuntimeCall,
-) -> Result<(u64, u64), Box<dyn std::error::Error>> {
-    let runtime_api_call = crate::asset_hub_runtime::apis()
+) -> Result<QueriedWeight, Box<dyn std::error::Error>> {
+    let runtime_api_call = super::asset_hub_runtime::apis()
         .transaction_payment_call_api()
         .query_call_info(call, 0);
-    let call_info = api
+    let block = context.asset_hub_block;
+    let call_info = context
+        .asset_hub_api
         .runtime_api()
-        .at_latest()
-        .await?
+        .at(block.hash)
         .call(runtime_api_call)
         .await?;
-    Ok((call_info.weight.ref_time, call_info.weight.proof_size))
+    Ok(QueriedWeight {
+        ref_time: call_info.weight.ref_time,
+        proof_size: call_info.weight.proof_size,
+        block_number: block.number,
+        block_hash: block.hash,
+    })
+}
+
+// Read a preimage generated by this tool, flattening a top-level batch into its calls
+pub fn read_preimage_calls(
+    path: &Path,
+) -> Result<Vec<RelayRuntimeCall>, Box<dyn std::error::Error>> {
+    let preimage = crate::decode::read_preimage(path)?;
+    let call = RelayRuntimeCall::decode(&mut preimage.as_slice())?;
+
+    match call {
+        RelayRuntimeCall::Utility(
+            super::relay_runtime::runtime_types::pallet_utility::pallet::Call::batch_all { calls },
+        ) => Ok(calls),
+        call => Ok(vec![call]),
+    }
+}
+
+// Priority of scheduled proposals, as for proposals enacted by OpenGov
+const SCHEDULE_PRIORITY: u8 = 63;
+
+// Schedule the call on the relay chain. The system parachains have no Scheduler, so calls on
+// them are scheduled by scheduling the XCM sending them.
+pub fn scheduled(call: RelayRuntimeCall, schedule: Schedule) -> RelayRuntimeCall {
+    let call = match schedule {
+        Schedule::At(when) => pallet_scheduler::pallet::Call::schedule {
+            when,
+            maybe_periodic: None,
+            priority: SCHEDULE_PRIORITY,
+            call: Box::new(call),
+        },
+        Schedule::After(after) => pallet_scheduler::pallet::Call::schedule_after {
+            after,
+            maybe_periodic: None,
+            priority: SCHEDULE_PRIORITY,
+            call: Box::new(call),
+        },
+    };
+    RelayRuntimeCall::Scheduler(call)
 }
 
 pub fn utility_force_batch(calls: Vec<RelayRuntimeCall>) -> RelayRuntimeCall {
     RelayRuntimeCall::Utility(
-        crate::relay_runtime::runtime_types::pallet_utility::pallet::Call::batch_all { calls },
+        super::relay_runtime::runtime_types::pallet_utility::pallet::Call::batch_all { calls },
     )
 }
 
-#[cfg(any(feature = "westend", feature = "paseo"))]
-pub fn sudo(call: Box<RelayRuntimeCall>) -> RelayRuntimeCall {
-    return RelayRuntimeCall::Sudo(
-        crate::relay_runtime::runtime_types::pallet_sudo::pallet::Call::sudo { call },
-    );
+// Amount with the given number of decimals, e.g. `1.500000000000` for 1.5 DOT
+pub fn units(amount: U256, decimals: u8) -> String {
+    let scale = U256::from(10).pow(U256::from(decimals));
+    let fraction = (amount % scale).to_string();
+    format!(
+        "{}.{fraction:0>width$}",
+        amount / scale,
+        width = decimals as usize
+    )
 }
 
-pub fn force_xcm_version() -> AssetHubRuntimeCall {
-    #[cfg(any(feature = "paseo", feature = "polkadot"))]
-    use crate::asset_hub_runtime::runtime_types::staging_xcm::v4::{
-        junction::Junction::GlobalConsensus, junction::NetworkId, junctions::Junctions::X1,
-        location::Location,
-    };
-    #[cfg(feature = "westend")]
-    use crate::asset_hub_runtime::runtime_types::staging_xcm::v5::{
-        junction::Junction::GlobalConsensus, junction::NetworkId, junctions::Junctions::X1,
-        location::Location,
-    };
-    let chain_id = crate::bridge_hub_runtime::CHAIN_ID;
-    let location = Box::new(Location {
-        parents: 2,
-        interior: X1([GlobalConsensus(NetworkId::Ethereum { chain_id })]),
-    });
-
-    AssetHubRuntimeCall::PolkadotXcm(
-        crate::asset_hub_runtime::runtime_types::pallet_xcm::pallet::Call::force_xcm_version {
-            location,
-            version: 4,
-        },
+// Amount of the relay chain token, followed by the amount in planck
+pub fn native(amount: u128) -> String {
+    format!(
+        "{} {} [{} PLANCK]",
+        units(U256::from(amount), NETWORK.decimals()),
+        NETWORK.symbol(),
+        amount
     )
 }
 
-use bridge_hub_runtime::runtime_types::snowbridge_core::outbound::v1::AgentExecuteCommand;
-use bridge_hub_runtime::runtime_types::snowbridge_core::outbound::v1::Command;
-use bridge_hub_runtime::runtime_types::snowbridge_core::outbound::Fee;
-
-pub async fn calculate_delivery_fee(
-    api: &OnlineClient<PolkadotConfig>,
-    params: &PricingParameters,
-) -> Result<Fee<u128>, Box<dyn std::error::Error>> {
-    let command = Command::AgentExecute {
-        agent_id: H256::zero(),
-        command: AgentExecuteCommand::TransferToken {
-            token: H160::zero(),
-            recipient: H160::zero(),
-            amount: 0,
-        },
-    };
-    let runtime_api_call = bridge_hub_runtime::apis()
-        .outbound_queue_api()
-        .calculate_fee(command, Some(params.clone()));
-    let fee = api
-        .runtime_api()
-        .at_latest()
-        .await?
-        .call(runtime_api_call)
-        .await?;
+// Amount of Ether, followed by the amount in wei
+pub fn ether(amount: U256) -> String {
+    format!("{} ETH [{} WEI]", units(amount, 18), amount)
+}
 
-    Ok(fee)
+pub fn gwei(amount: U256) -> String {
+    format!("{} GWEI", units(amount, 9))
+}
+
+pub fn fixed(value: FixedU128) -> String {
+    units(U256::from(value.into_inner()), 18)
+}
+
+// Price of the relay chain token in ETH
+pub fn exchange_rate(value: FixedU128) -> String {
+    format!("{} ETH/{}", fixed(value), NETWORK.symbol())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chains(calls: &Calls) -> Vec<(ChainEnum, usize)> {
+        calls
+            .0
+            .iter()
+            .map(|calls| match calls {
+                ChainCalls::BridgeHub(calls) => (ChainEnum::BridgeHub, calls.len()),
+                ChainCalls::AssetHub(calls) => (ChainEnum::AssetHub, calls.len()),
+                ChainCalls::Relay(calls) => (ChainEnum::Relay, calls.len()),
+            })
+            .collect()
+    }
+
+    fn bridge_hub_call() -> BridgeHubRuntimeCall {
+        BridgeHubRuntimeCall::System(
+            bridge_hub_runtime::runtime_types::frame_system::pallet::Call::remark {
+                remark: vec![],
+            },
+        )
+    }
+
+    fn asset_hub_call() -> AssetHubRuntimeCall {
+        AssetHubRuntimeCall::System(
+            super::super::asset_hub_runtime::runtime_types::frame_system::pallet::Call::remark {
+                remark: vec![],
+            },
+        )
+    }
+
+    fn relay_call() -> RelayRuntimeCall {
+        RelayRuntimeCall::System(
+            super::super::relay_runtime::runtime_types::frame_system::pallet::Call::remark {
+                remark: vec![],
+            },
+        )
+    }
+
+    #[test]
+    fn keeps_the_order_of_calls_across_chains() {
+        let mut calls = Calls::bridge_hub(vec![bridge_hub_call(), bridge_hub_call()]);
+        calls.push_asset_hub(asset_hub_call());
+        calls.push_bridge_hub(bridge_hub_call());
+        calls.extend(Calls::relay(vec![relay_call()]));
+        calls.extend(Calls::relay(vec![relay_call()]));
+
+        assert_eq!(
+            chains(&calls),
+            [
+                (ChainEnum::BridgeHub, 2),
+                (ChainEnum::AssetHub, 1),
+                (ChainEnum::BridgeHub, 1),
+                (ChainEnum::Relay, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_consecutive_calls_on_the_same_chain() {
+        let mut calls = Calls::default();
+        calls.extend(Calls::asset_hub(vec![asset_hub_call()]));
+        calls.extend(Calls::bridge_hub(vec![]));
+        calls.extend(Calls::asset_hub(vec![asset_hub_call(), asset_hub_call()]));
+
+        assert_eq!(chains(&calls), [(ChainEnum::AssetHub, 3)]);
+        assert!(Calls::relay(vec![]).is_empty());
+    }
+
+    #[test]
+    fn formats_units() {
+        assert_eq!(units(U256::from(15_000_000_000u64), 10), "1.5000000000");
+        assert_eq!(units(U256::from(42), 12), "0.000000000042");
+        assert_eq!(units(U256::ZERO, 0), "0.0");
+        assert_eq!(gwei(U256::from(1_500_000_000u64)), "1.500000000 GWEI");
+        assert_eq!(
+            ether(U256::from(10).pow(U256::from(17))),
+            "0.100000000000000000 ETH [100000000000000000 WEI]"
+        );
+        assert_eq!(
+            fixed(FixedU128::from_inner(250_000_000_000_000_000)),
+            "0.250000000000000000"
+        );
+    }
 }
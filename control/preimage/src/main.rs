@@ -1,39 +1,75 @@
-mod asset_hub_runtime;
-mod bridge_hub_runtime;
-mod commands;
-mod constants;
-mod helpers;
-mod relay_runtime;
-mod treasury_commands;
-
-use alloy_primitives::{utils::parse_units, Address, Bytes, FixedBytes, U128, U256};
-use snowbridge_preimage_chopsticks::generate_chopsticks_script;
+mod beacon;
+mod decode;
+mod diff;
+mod dry_run;
+mod dynamic;
+mod ethereum;
+mod manifest;
+mod network;
+mod opengov;
+mod provenance;
+mod schedule;
+#[cfg(test)]
+mod stub;
+mod verify;
+
+use alloy_primitives::{utils::parse_units, Address, Bytes, FixedBytes, U256};
+use snowbridge_preimage_chopsticks::{generate_chopsticks_script, Schedule};
 use clap::{Args, Parser, Subcommand, ValueEnum};
-use codec::Encode;
-use constants::{ASSET_HUB_API, BRIDGE_HUB_API, POLKADOT_DECIMALS, POLKADOT_SYMBOL, RELAY_API};
-use helpers::{force_xcm_version, send_xcm_asset_hub, send_xcm_bridge_hub, utility_force_batch};
+use ethereum::EthereumRpc;
+use network::{with_network, with_snowbridge, Network};
+use opengov::OpenGovTrack;
 use sp_crypto_hashing::blake2_256;
-use std::{io::Write, path::PathBuf};
-use subxt::{OnlineClient, PolkadotConfig};
-
-#[cfg(any(feature = "westend", feature = "paseo"))]
-use crate::helpers::sudo;
+use std::{
+    io::{IsTerminal, Write},
+    path::PathBuf,
+    sync::Mutex,
+    time::SystemTime,
+};
+use subxt::{utils::H256, OnlineClient, PolkadotConfig};
 
 #[derive(Debug, Parser)]
 #[command(name = "snowbridge-preimage", version, about, long_about = None)]
 struct Cli {
+    /// Network to build the preimage for
+    #[arg(long, value_enum)]
+    network: Network,
+
     /// Output format of preimage
     #[arg(long, value_enum, default_value_t=Format::Hex)]
     format: Format,
 
-    /// Wrap preimage in a sudo call
-    #[cfg(any(feature = "westend", feature = "paseo"))]
+    /// Wrap preimage in a sudo call, on networks with a sudo key
     #[arg(long, default_value_t = false)]
     sudo: bool,
 
+    /// Dry-run the preimage with Root origin on the relay chain, and the XCMs it sends on
+    /// BridgeHub and AssetHub, before printing it
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Print the calls submitting the preimage to an OpenGov track, instead of the preimage
+    #[arg(long, value_enum, value_name = "TRACK", conflicts_with = "sudo")]
+    opengov: Option<OpenGovTrack>,
+
+    /// Number of blocks after the referendum passes that the proposal is enacted
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        default_value_t = 10u32,
+        requires = "opengov"
+    )]
+    enactment_after: u32,
+
+    #[command(flatten)]
+    schedule: ScheduleArgs,
+
     #[command(flatten)]
     api_endpoints: ApiEndpoints,
 
+    #[command(flatten)]
+    pinned_blocks: PinnedBlocks,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -50,18 +86,57 @@ pub enum Command {
     GatewayOperatingMode(GatewayOperatingModeArgs),
     /// Set pricing parameters
     PricingParameters(PricingParametersArgs),
+    /// Set token transfer fees
+    TokenTransferFees(TokenTransferFeesArgs),
     /// Set the checkpoint for the beacon light client
     ForceCheckpoint(ForceCheckpointArgs),
+    /// Verify a checkpoint for the beacon light client
+    VerifyCheckpoint(ForceCheckpointArgs),
+    /// Print the operating modes, fees and beacon checkpoint of the bridge
+    Status(StatusArgs),
+    /// Compare a desired state of the bridge with the chains, and build the calls changing it
+    Plan(PlanArgs),
+    /// List the assets bridged from Ethereum to AssetHub and the Polkadot-native assets
+    /// registered on Ethereum
+    Assets(AssetsArgs),
+    /// Quote the fees of the messages sent over the bridge, with the current pricing parameters
+    /// and optionally with proposed ones
+    QuoteFees(QuoteFeesArgs),
+    /// Suggest pricing parameters from the price of ETH and the Ethereum fee history
+    SuggestPricing(SuggestPricingArgs),
     /// Set the checkpoint for the beacon light client
     HaltBridge(HaltBridgeArgs),
+    /// Resume the bridge after it was halted
+    ResumeBridge(ResumeBridgeArgs),
     /// Register Ether
     RegisterEther(RegisterEtherArgs),
-    /// Treasury proposal
-    TreasuryProposal2024(TreasuryProposal2024Args),
-    /// Governance update 202501
-    GovUpdate202501(GovUpdate202501Args),
-    /// Register PNA
-    RegisterPnaBatch202503,
+    /// Transfer Ether held by an agent to a recipient on Ethereum
+    AgentTransfer(AgentTransferArgs),
+    /// Authorize a runtime upgrade on BridgeHub or AssetHub
+    UpgradeRuntime(UpgradeRuntimeArgs),
+    /// Call any pallet on the relay chain, BridgeHub or AssetHub, with arguments as JSON
+    Call(CallArgs),
+    /// Register Polkadot-native assets listed in a JSON or TOML file
+    RegisterPna(RegisterPnaArgs),
+    /// Register ERC20 tokens on AssetHub listed in a JSON or TOML file
+    RegisterErc20(RegisterErc20Args),
+    /// Build a proposal from the steps listed in a TOML manifest
+    Build(BuildArgs),
+    /// Decode a preimage into the tree of calls it dispatches on each chain
+    Decode(DecodeArgs),
+    /// Compare the calls of two preimages
+    Diff(DiffArgs),
+    /// Regenerate a preimage from its JSON manifest, at the blocks it was built at, and check
+    /// that it matches
+    Verify(VerifyArgs),
+}
+
+// A manifest step, parsed like a subcommand of the CLI
+#[derive(Debug, Parser)]
+#[command(no_binary_name = true)]
+struct ManifestStep {
+    #[command(subcommand)]
+    command: Command,
 }
 
 #[derive(Debug, Args)]
@@ -114,12 +189,73 @@ pub struct UpgradeArgs {
     logic_code_hash: FixedBytes<32>,
 
     /// ABI-encoded params to pass to initializer
-    #[arg(long, value_name = "BYTES", value_parser=parse_hex_bytes)]
-    initializer_params: Bytes,
+    #[arg(
+        long,
+        value_name = "BYTES",
+        value_parser=parse_hex_bytes,
+        required_unless_present = "initializer_json",
+        conflicts_with = "initializer_json"
+    )]
+    initializer_params: Option<Bytes>,
+
+    /// Params to pass to initializer as a JSON object keyed by parameter name, or a JSON array
+    #[arg(
+        long,
+        value_name = "JSON",
+        value_parser=parse_json,
+        requires = "initializer_layout"
+    )]
+    initializer_json: Option<serde_json::Value>,
+
+    #[command(flatten)]
+    initializer_layout: InitializerLayoutArgs,
+
+    /// Function in the contract ABI whose inputs describe the initializer params
+    #[arg(long, value_name = "NAME", requires = "initializer_abi")]
+    initializer_function: Option<String>,
 
     /// Maximum gas required by the initializer
     #[arg(long, value_name = "GAS")]
     initializer_gas: u64,
+
+    /// Ethereum RPC endpoint used to check the logic contract and initializer gas
+    #[arg(long, value_name = "URL")]
+    ethereum_rpc: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[group(id = "initializer_layout", multiple = false)]
+pub struct InitializerLayoutArgs {
+    /// Solidity parameter list of the initializer params, e.g. "(uint8 mode, uint128 fee)"
+    #[arg(long, value_name = "SIGNATURE")]
+    initializer_type: Option<String>,
+
+    /// Contract ABI JSON, such as a build artifact in contracts/out, with a function taking
+    /// the initializer params
+    #[arg(long, value_name = "FILE", requires = "initializer_function")]
+    initializer_abi: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct UpgradeRuntimeArgs {
+    /// Path to the compiled runtime, e.g. bridge_hub_polkadot_runtime.compact.compressed.wasm
+    #[arg(long, value_name = "FILE")]
+    wasm: PathBuf,
+
+    /// Chain to upgrade
+    #[arg(long, value_enum)]
+    chain: ParachainEnum,
+
+    /// Preimage generated by this tool, to be batched after the upgrade authorization. Can be
+    /// repeated.
+    #[arg(long, value_name = "FILE")]
+    then: Vec<PathBuf>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum ParachainEnum {
+    BridgeHub,
+    AssetHub,
 }
 
 #[derive(Debug, Args)]
@@ -151,8 +287,32 @@ pub struct GatewayAddressArgs {
 #[derive(Debug, Args)]
 pub struct ForceCheckpointArgs {
     /// Path to JSON file containing checkpoint
-    #[arg(long, value_name = "FILE")]
-    pub checkpoint: PathBuf,
+    #[arg(
+        long,
+        value_name = "FILE",
+        required_unless_present = "beacon_url",
+        conflicts_with = "beacon_url"
+    )]
+    pub checkpoint: Option<PathBuf>,
+    /// Beacon node REST API used to build the checkpoint. The node must serve the debug API
+    /// (`/eth/v2/debug/beacon/states`), as the block roots are proven from the beacon state.
+    #[arg(long, value_name = "URL")]
+    pub beacon_url: Option<String>,
+    /// Slot of the checkpoint block. Defaults to the latest finalized block.
+    #[arg(
+        long,
+        value_name = "SLOT",
+        requires = "beacon_url",
+        conflicts_with = "block_root"
+    )]
+    pub slot: Option<u64>,
+    /// Root of the checkpoint block. Defaults to the latest finalized block.
+    #[arg(long, value_name = "HASH", value_parser=parse_hex_bytes32, requires = "beacon_url")]
+    pub block_root: Option<FixedBytes<32>>,
+    /// Warn when the checkpoint is older than the finalized beacon state on BridgeHub by more
+    /// than this number of epochs
+    #[arg(long, value_name = "EPOCHS", default_value_t = 256u64)]
+    pub weak_subjectivity_epochs: u64,
 }
 
 #[derive(Debug, Args)]
@@ -181,13 +341,26 @@ pub struct PricingParametersArgs {
     #[arg(long, value_name = "GWEI", value_parser = parse_units_gwei)]
     pub fee_per_gas: U256,
     /// Relayer reward for delivering messages to Polkadot
-    #[arg(long, value_name = POLKADOT_SYMBOL, value_parser = parse_units_polkadot)]
-    pub local_reward: U128,
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_native_amount)]
+    pub local_reward: NativeAmount,
     /// Relayer reward for delivering messages to Ethereum
     #[arg(long, value_name = "ETHER", value_parser = parse_units_eth)]
     pub remote_reward: U256,
 }
 
+#[derive(Debug, Args)]
+pub struct TokenTransferFeesArgs {
+    /// Fee for the XCM which creates a registered token on AssetHub
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_native_amount)]
+    pub create_asset_xcm: NativeAmount,
+    /// Fee for the XCM which reserve-transfers a token to AssetHub
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_native_amount)]
+    pub transfer_asset_xcm: NativeAmount,
+    /// Fee charged on Ethereum for registering a token
+    #[arg(long, value_name = "ETHER", value_parser = parse_units_eth)]
+    pub register_token: U256,
+}
+
 #[derive(Debug, Args)]
 pub struct HaltBridgeArgs {
     /// Halt the Ethereum gateway, blocking message from Ethereum to Polkadot in the Ethereum
@@ -213,18 +386,30 @@ pub struct HaltBridgeArgs {
 }
 
 #[derive(Debug, Args)]
-pub struct TreasuryProposal2024Args {
-    /// Beneficiary address
-    #[arg(long, value_name = "ADDRESS", value_parser=parse_hex_bytes32)]
-    beneficiary: FixedBytes<32>,
-}
-
-#[derive(Debug, Args)]
-pub struct GovUpdate202501Args {
-    #[command(flatten)]
-    pricing_parameters: PricingParametersArgs,
-    #[command(flatten)]
-    register_ether: RegisterEtherArgs,
+pub struct ResumeBridgeArgs {
+    /// Resume the Ethereum gateway, allowing messages from Ethereum to Polkadot in the Ethereum
+    /// contract.
+    #[arg(long, value_name = "RESUME_GATEWAY")]
+    gateway: bool,
+    /// Resume the Ethereum Inbound Queue, allowing messages from BH to AH.
+    #[arg(long, value_name = "RESUME_INBOUND_QUEUE")]
+    inbound_queue: bool,
+    /// Resume the Ethereum Outbound Queue, allowing messages from AH to BH.
+    #[arg(long, value_name = "RESUME_OUTBOUND_QUEUE")]
+    outbound_queue: bool,
+    /// Resume the Ethereum client, allowing consensus updates to the light client.
+    #[arg(long, value_name = "RESUME_ETHEREUM_CLIENT")]
+    ethereum_client: bool,
+    /// Restore the AH to Ethereum fee. Unless `--assethub-base-fee` is given, the fee is
+    /// recomputed from the current pricing parameters on BridgeHub.
+    #[arg(long, value_name = "RESTORE_ASSETHUB_FEE")]
+    assethub_fee: bool,
+    /// The AH to Ethereum fee to restore.
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_native_amount)]
+    assethub_base_fee: Option<NativeAmount>,
+    /// Resume all parts of the bridge
+    #[arg(long, value_name = "RESUME_SNOWBRIDGE")]
+    all: bool,
 }
 
 #[derive(Debug, Args)]
@@ -243,6 +428,43 @@ pub struct RegisterEtherArgs {
     ether_decimals: u8,
 }
 
+#[derive(Debug, Args)]
+pub struct RegisterPnaArgs {
+    /// Path to JSON or TOML file containing the assets to register
+    #[arg(long, value_name = "FILE")]
+    pub assets: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct RegisterErc20Args {
+    /// Path to JSON or TOML file containing the tokens to register
+    #[arg(long, value_name = "FILE")]
+    pub assets: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct AgentTransferArgs {
+    /// Location of the agent's owner relative to BridgeHub, as JSON, e.g.
+    /// '{"parents":1,"interior":{"X1":[{"Parachain":2000}]}}'
+    #[arg(
+        long,
+        value_name = "JSON",
+        value_parser = parse_location,
+        required_unless_present = "agent_id",
+        conflicts_with = "agent_id"
+    )]
+    pub location: Option<xcm::v4::Location>,
+    /// ID of the agent, resolved to its owner using the channels registered on BridgeHub
+    #[arg(long, value_name = "HASH", value_parser=parse_hex_bytes32)]
+    pub agent_id: Option<FixedBytes<32>>,
+    /// Recipient of the Ether on Ethereum
+    #[arg(long, value_name = "ADDRESS", value_parser=parse_eth_address)]
+    pub recipient: Address,
+    /// Amount of Ether to transfer
+    #[arg(long, value_name = "ETHER", value_parser = parse_units_eth)]
+    pub amount: U256,
+}
+
 #[derive(Debug, Args)]
 pub struct ApiEndpoints {
     #[arg(long, value_name = "URL")]
@@ -255,6 +477,54 @@ pub struct ApiEndpoints {
     relay_api: Option<String>,
 }
 
+/// Blocks to query the chains at, instead of their latest finalized blocks, to regenerate a
+/// preimage from the same chain state
+#[derive(Debug, Args)]
+pub struct PinnedBlocks {
+    #[arg(long, value_name = "HASH", value_parser=parse_hex_bytes32)]
+    bridge_hub_at: Option<FixedBytes<32>>,
+
+    #[arg(long, value_name = "HASH", value_parser=parse_hex_bytes32)]
+    asset_hub_at: Option<FixedBytes<32>>,
+
+    #[arg(long, value_name = "HASH", value_parser=parse_hex_bytes32)]
+    relay_at: Option<FixedBytes<32>>,
+
+    /// Ethereum block to query the Gateway at, instead of the latest block
+    #[arg(long, value_name = "BLOCK")]
+    ethereum_at: Option<u64>,
+
+    /// Root of the beacon block to build checkpoints at when neither `--slot` nor `--block-root`
+    /// is given, instead of the latest finalized block
+    #[arg(long, value_name = "HASH", value_parser=parse_hex_bytes32)]
+    beacon_at: Option<FixedBytes<32>>,
+}
+
+/// Schedule the proposal on the relay chain with `Scheduler.schedule` or `schedule_after`, so that
+/// it is dispatched at a given block rather than when it is enacted.
+///
+/// Only the relay chain Scheduler is used. Calls on BridgeHub and AssetHub, which have no
+/// Scheduler, are executed when the parachain processes the XCM sent at the scheduled relay chain
+/// block, not at a block of the parachain.
+#[derive(Debug, Args)]
+#[group(multiple = false)]
+pub struct ScheduleArgs {
+    /// Dispatch the proposal at the given relay chain block. Calls on BridgeHub and AssetHub
+    /// are executed when the parachain processes the XCM sent at that block, as only the relay
+    /// chain has a Scheduler.
+    #[arg(long, value_name = "BLOCK")]
+    schedule_at: Option<u32>,
+
+    /// Dispatch the proposal the given number of relay chain blocks after it is enacted
+    #[arg(long, value_name = "BLOCKS")]
+    schedule_after: Option<u32>,
+
+    /// Dispatch the proposal at the relay chain block expected at the given UTC time, e.g.
+    /// 2025-05-07T12:00:00Z
+    #[arg(long, value_name = "TIME", value_parser = parse_time)]
+    schedule_at_time: Option<SystemTime>,
+}
+
 fn parse_eth_address(v: &str) -> Result<Address, String> {
     Address::parse_checksummed(v, None).map_err(|_| "invalid ethereum address".to_owned())
 }
@@ -275,11 +545,53 @@ fn parse_hex_bytes(v: &str) -> Result<Bytes, String> {
         .map_err(|_| "invalid hex value".to_owned())
 }
 
-fn parse_units_polkadot(v: &str) -> Result<U128, String> {
-    let amount = parse_units(v, POLKADOT_DECIMALS).map_err(|e| format!("{e}"))?;
-    let amount: U256 = amount.into();
-    let amount: U128 = amount.to::<U128>();
-    Ok(amount)
+fn parse_var(v: &str) -> Result<(String, String), String> {
+    v.split_once('=')
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .ok_or_else(|| "expected NAME=VALUE".to_owned())
+}
+
+fn parse_json(v: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(v).map_err(|e| format!("invalid JSON: {e}"))
+}
+
+fn parse_time(v: &str) -> Result<SystemTime, String> {
+    humantime::parse_rfc3339_weak(v).map_err(|e| format!("invalid time: {e}"))
+}
+
+fn parse_location(v: &str) -> Result<xcm::v4::Location, String> {
+    serde_json::from_str(v).map_err(|e| format!("invalid XCM location: {e}"))
+}
+
+/// Amount of the relay chain token, which is converted to plancks once the network is known
+#[derive(Clone, Debug)]
+pub struct NativeAmount(String);
+
+impl NativeAmount {
+    pub fn planck(&self, network: Network) -> Result<u128, String> {
+        let amount = parse_decimals(&self.0, network.decimals())
+            .map_err(|e| format!("Invalid {} amount {}: {e}", network.symbol(), self.0))?;
+        amount
+            .try_into()
+            .map_err(|_| format!("{} amount {} is too large", network.symbol(), self.0))
+    }
+}
+
+// Check the amount parses with the smallest unit of any network
+fn parse_native_amount(v: &str) -> Result<NativeAmount, String> {
+    parse_decimals(v, 12)?;
+    Ok(NativeAmount(v.to_owned()))
+}
+
+// Parse an amount with at most `decimals` decimal places, which `parse_units` would truncate
+fn parse_decimals(v: &str, decimals: u8) -> Result<U256, String> {
+    if let Some((_, fraction)) = v.split_once('.') {
+        if fraction.len() > decimals as usize {
+            return Err(format!("more than {decimals} decimal places"));
+        }
+    }
+    let amount = parse_units(v, decimals).map_err(|e| format!("{e}"))?;
+    Ok(amount.into())
 }
 
 fn parse_units_gwei(v: &str) -> Result<U256, String> {
@@ -292,219 +604,603 @@ fn parse_units_eth(v: &str) -> Result<U256, String> {
     Ok(amount.into())
 }
 
+// Prices are kept as fixed-point numbers with 18 decimals, so that their ratios are exact
+fn parse_price(v: &str) -> Result<U256, String> {
+    let price: U256 = parse_units(v, 18).map_err(|e| format!("{e}"))?.into();
+    if price.is_zero() {
+        return Err("price must be positive".into());
+    }
+    Ok(price)
+}
+
+#[derive(Debug, Args)]
+pub struct BuildArgs {
+    /// Path to TOML file listing the steps of the proposal
+    #[arg(long, value_name = "FILE")]
+    manifest: PathBuf,
+
+    /// Value substituted for `${NAME}` in the manifest
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser=parse_var)]
+    vars: Vec<(String, String)>,
+}
+
+#[derive(Debug, Args)]
+pub struct DecodeArgs {
+    /// Preimage as hex
+    #[arg(
+        value_name = "HEX",
+        value_parser=parse_hex_bytes,
+        required_unless_present = "file",
+        conflicts_with = "file"
+    )]
+    preimage: Option<Bytes>,
+
+    /// Path to a preimage written by this tool, in hex or binary format
+    #[arg(long, value_name = "FILE")]
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// Path to the original preimage, in hex or binary format
+    #[arg(value_name = "A")]
+    a: PathBuf,
+
+    /// Path to the preimage to compare it with, in hex or binary format
+    #[arg(value_name = "B")]
+    b: PathBuf,
+
+    /// Print the changes as JSON
+    #[arg(long, default_value_t = false)]
+    json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct StatusArgs {
+    /// Ethereum RPC endpoint used to read the Gateway operating mode and token transfer fees
+    #[arg(long, value_name = "URL")]
+    ethereum_rpc: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct AssetsArgs {
+    /// Print the assets as JSON
+    #[arg(long, default_value_t = false)]
+    json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct QuoteFeesArgs {
+    // Pricing parameters to quote the fees with besides the current ones, given as for
+    // `pricing-parameters`
+    #[command(flatten)]
+    proposed: Option<PricingParametersArgs>,
+
+    /// Ethereum RPC endpoint used to quote the fees charged by the Gateway for messages to
+    /// Polkadot
+    #[arg(long, value_name = "URL")]
+    ethereum_rpc: Option<String>,
+
+    /// Registered ERC20 token to quote transfers from Ethereum to AssetHub for
+    #[arg(long, value_name = "ADDRESS", requires = "ethereum_rpc")]
+    token: Option<Address>,
+}
+
+#[derive(Debug, Args)]
+pub struct SuggestPricingArgs {
+    /// Price of 1 ETH in the relay chain token, e.g. 400 if 1 ETH buys 400 DOT
+    #[arg(
+        long,
+        value_name = "PRICE",
+        value_parser = parse_price,
+        required_unless_present = "eth_usd",
+        conflicts_with_all = ["eth_usd", "native_usd"]
+    )]
+    eth_price: Option<U256>,
+
+    /// Price of 1 ETH in USD, given with `--native-usd` instead of `--eth-price`
+    #[arg(long, value_name = "PRICE", value_parser = parse_price, requires = "native_usd")]
+    eth_usd: Option<U256>,
+
+    /// Price of 1 relay chain token in USD
+    #[arg(long, value_name = "PRICE", value_parser = parse_price, requires = "eth_usd")]
+    native_usd: Option<U256>,
+
+    /// Ethereum RPC endpoint to read the fee history from, such as a local anvil node
+    #[arg(long, value_name = "URL")]
+    ethereum_rpc: String,
+
+    /// Number of recent Ethereum blocks to read the fee history of
+    #[arg(long, value_name = "BLOCKS", default_value_t = 7200)]
+    blocks: u64,
+
+    /// Percentile of the priority fees paid in each block, weighted by gas used
+    #[arg(long, value_name = "PERCENTILE", default_value_t = 50.0)]
+    reward_percentile: f64,
+
+    /// Percentile of the gas prices over the blocks suggested as the fee per gas
+    #[arg(long, value_name = "PERCENTILE", default_value_t = 90.0)]
+    percentile: f64,
+
+    /// Numerator for Multiplier, defaulting to the current multiplier
+    #[arg(long, value_name = "UINT", requires = "multiplier_denominator")]
+    multiplier_numerator: Option<u64>,
+
+    /// Denominator for Multiplier
+    #[arg(long, value_name = "UINT", requires = "multiplier_numerator")]
+    multiplier_denominator: Option<u64>,
+
+    /// Relayer reward for delivering messages to Polkadot, defaulting to the current reward
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_native_amount)]
+    local_reward: Option<NativeAmount>,
+
+    /// Relayer reward for delivering messages to Ethereum, defaulting to the current reward
+    #[arg(long, value_name = "ETHER", value_parser = parse_units_eth)]
+    remote_reward: Option<U256>,
+}
+
+#[derive(Debug, Args)]
+pub struct PlanArgs {
+    /// TOML file with the desired state, keyed like the options of the commands setting it
+    #[arg(value_name = "FILE")]
+    state: PathBuf,
+
+    /// Build a preimage applying the changes, instead of only printing them
+    #[arg(long)]
+    emit: bool,
+
+    /// Ethereum RPC endpoint used to read the Gateway operating mode and token transfer fees
+    #[arg(long, value_name = "URL")]
+    ethereum_rpc: Option<String>,
+}
+
+// The desired state in a plan, parsed like the options of the commands setting it. Each group is
+// optional, and left unchanged when not given.
+#[derive(Debug, Parser)]
+#[command(no_binary_name = true)]
+struct DesiredState {
+    #[command(flatten)]
+    pricing_parameters: Option<PricingParametersArgs>,
+
+    #[command(flatten)]
+    token_transfer_fees: Option<TokenTransferFeesArgs>,
+
+    #[command(flatten)]
+    gateway_operating_mode: Option<GatewayOperatingModeArgs>,
+
+    /// Operating mode of the inbound queue
+    #[arg(long, value_enum)]
+    inbound_queue_operating_mode: Option<OperatingModeEnum>,
+
+    /// Operating mode of the outbound queue
+    #[arg(long, value_enum)]
+    outbound_queue_operating_mode: Option<OperatingModeEnum>,
+
+    /// Operating mode of the beacon light client
+    #[arg(long, value_enum)]
+    ethereum_client_operating_mode: Option<OperatingModeEnum>,
+
+    #[command(flatten)]
+    gateway_address: Option<GatewayAddressArgs>,
+
+    /// Fee charged by AssetHub for sending a message to Ethereum. Defaults to the fee computed
+    /// from `pricing-parameters` when they are given.
+    #[arg(long, value_name = "AMOUNT", value_parser = parse_native_amount)]
+    assethub_base_fee: Option<NativeAmount>,
+
+    // ERC20 tokens expected to be registered on AssetHub, in the file format of `register-erc20`
+    #[command(flatten)]
+    assets: Option<RegisterErc20Args>,
+}
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// Manifest printed with `--format json` when the preimage was built
+    #[arg(value_name = "FILE")]
+    manifest: PathBuf,
+
+    /// Expected preimage hash, e.g. of a referendum, instead of the hash in the manifest
+    #[arg(long, value_name = "HASH", value_parser=parse_hex_bytes32)]
+    hash: Option<FixedBytes<32>>,
+}
+
+#[derive(Debug, Args)]
+pub struct CallArgs {
+    /// Chain to dispatch the call on
+    #[arg(long, value_enum)]
+    chain: ChainEnum,
+
+    /// Name of the pallet, e.g. EthereumSystem
+    #[arg(long, value_name = "PALLET")]
+    pallet: String,
+
+    /// Name of the call, e.g. set_operating_mode
+    #[arg(long, value_name = "CALL")]
+    call: String,
+
+    /// Call arguments as a JSON object keyed by field name, or a JSON array
+    #[arg(long, value_name = "JSON", value_parser=parse_json, default_value = "{}")]
+    args: serde_json::Value,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainEnum {
+    Relay,
+    BridgeHub,
+    AssetHub,
+}
+
+impl std::fmt::Display for ChainEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ChainEnum::Relay => "Relay",
+            ChainEnum::BridgeHub => "BridgeHub",
+            ChainEnum::AssetHub => "AssetHub",
+        };
+        f.write_str(name)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
 pub enum Format {
     Hex,
     Binary,
+    /// The preimage with its hash, decoded calls, and a manifest of the values derived from
+    /// the chains and the runtimes and tool revision it was built with
+    Json,
 }
 
 struct Context {
+    network: Network,
     bridge_hub_api: Box<OnlineClient<PolkadotConfig>>,
     asset_hub_api: Box<OnlineClient<PolkadotConfig>>,
-    _relay_api: Box<OnlineClient<PolkadotConfig>>,
+    relay_api: Box<OnlineClient<PolkadotConfig>>,
+    // Blocks all queries of each chain are made at, so that the preimage is built from a
+    // consistent chain state which can be queried again
+    bridge_hub_block: PinnedBlock,
+    asset_hub_block: PinnedBlock,
+    relay_block: PinnedBlock,
+    // Ethereum block and beacon block root, given or resolved when first queried, so that all
+    // queries are made at the same block and it can be recorded in the manifest
+    ethereum_block: Mutex<Option<u64>>,
+    beacon_block_root: Mutex<Option<FixedBytes<32>>>,
 }
 
-#[tokio::main]
-async fn main() {
-    if let Err(err) = run().await {
-        eprintln!("{err}");
+impl Context {
+    fn api(&self, chain: ChainEnum) -> &OnlineClient<PolkadotConfig> {
+        match chain {
+            ChainEnum::Relay => &self.relay_api,
+            ChainEnum::BridgeHub => &self.bridge_hub_api,
+            ChainEnum::AssetHub => &self.asset_hub_api,
+        }
+    }
+
+    fn block(&self, chain: ChainEnum) -> PinnedBlock {
+        match chain {
+            ChainEnum::Relay => self.relay_block,
+            ChainEnum::BridgeHub => self.bridge_hub_block,
+            ChainEnum::AssetHub => self.asset_hub_block,
+        }
+    }
+
+    // Client of an Ethereum node querying the pinned block, or the latest block at the first
+    // query
+    async fn ethereum(&self, url: &str) -> Result<EthereumRpc, Box<dyn std::error::Error>> {
+        let rpc = EthereumRpc::new(url);
+        let pinned = *self.ethereum_block.lock().expect("not poisoned");
+        let block = match pinned {
+            Some(block) => block,
+            None => {
+                let latest = rpc.block_number().await?;
+                *self
+                    .ethereum_block
+                    .lock()
+                    .expect("not poisoned")
+                    .get_or_insert(latest)
+            }
+        };
+        Ok(rpc.at(block))
+    }
+
+    // Root of the pinned beacon block, or the latest finalized block at the first query
+    async fn beacon_block_root(
+        &self,
+        beacon_url: &str,
+    ) -> Result<FixedBytes<32>, Box<dyn std::error::Error>> {
+        let pinned = *self.beacon_block_root.lock().expect("not poisoned");
+        match pinned {
+            Some(block_root) => Ok(block_root),
+            None => {
+                let finalized = beacon::finalized_block_root(beacon_url).await?;
+                Ok(*self
+                    .beacon_block_root
+                    .lock()
+                    .expect("not poisoned")
+                    .get_or_insert(finalized))
+            }
+        }
     }
 }
 
-async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+/// Block a chain is queried at
+#[derive(Copy, Clone, Debug, serde::Serialize)]
+pub struct PinnedBlock {
+    pub number: u32,
+    pub hash: H256,
+}
 
+// Connect to the chains, pinning each to the given block or its latest finalized block
+async fn connect(
+    network: Network,
+    endpoints: &ApiEndpoints,
+    pinned_blocks: &PinnedBlocks,
+) -> Result<Context, Box<dyn std::error::Error>> {
     let bridge_hub_api: OnlineClient<PolkadotConfig> = OnlineClient::from_url(
-        cli.api_endpoints
+        endpoints
             .bridge_hub_api
-            .unwrap_or(BRIDGE_HUB_API.to_owned()),
+            .clone()
+            .unwrap_or(network.bridge_hub_api().to_owned()),
     )
     .await?;
 
     let asset_hub_api: OnlineClient<PolkadotConfig> = OnlineClient::from_url(
-        cli.api_endpoints
+        endpoints
             .asset_hub_api
-            .unwrap_or(ASSET_HUB_API.to_owned()),
+            .clone()
+            .unwrap_or(network.asset_hub_api().to_owned()),
     )
     .await?;
 
-    let relay_api: OnlineClient<PolkadotConfig> =
-        OnlineClient::from_url(cli.api_endpoints.relay_api.unwrap_or(RELAY_API.to_owned())).await?;
+    let relay_api: OnlineClient<PolkadotConfig> = OnlineClient::from_url(
+        endpoints
+            .relay_api
+            .clone()
+            .unwrap_or(network.relay_api().to_owned()),
+    )
+    .await?;
 
-    let context = Context {
+    Ok(Context {
+        network,
+        bridge_hub_block: pin(&bridge_hub_api, pinned_blocks.bridge_hub_at).await?,
+        asset_hub_block: pin(&asset_hub_api, pinned_blocks.asset_hub_at).await?,
+        relay_block: pin(&relay_api, pinned_blocks.relay_at).await?,
+        ethereum_block: Mutex::new(pinned_blocks.ethereum_at),
+        beacon_block_root: Mutex::new(pinned_blocks.beacon_at),
         bridge_hub_api: Box::new(bridge_hub_api),
         asset_hub_api: Box::new(asset_hub_api),
-        _relay_api: Box::new(relay_api),
+        relay_api: Box::new(relay_api),
+    })
+}
+
+async fn pin(
+    api: &OnlineClient<PolkadotConfig>,
+    hash: Option<FixedBytes<32>>,
+) -> Result<PinnedBlock, Box<dyn std::error::Error>> {
+    let hash = match hash {
+        Some(hash) => H256(hash.0),
+        None => api.backend().latest_finalized_block_ref().await?.hash(),
     };
+    let block = api
+        .blocks()
+        .at(hash)
+        .await
+        .map_err(|e| format!("Block {hash:?} not found: {e}"))?;
+    Ok(PinnedBlock {
+        number: block.number(),
+        hash,
+    })
+}
 
-    let call = match &cli.command {
-        Command::ForceCheckpoint(params) => {
-            let call = commands::force_checkpoint(params);
-            send_xcm_bridge_hub(&context, vec![call]).await?
-        }
-        Command::Initialize(params) => {
-            let (set_pricing_parameters, set_ethereum_fee) =
-                commands::pricing_parameters(&context, &params.pricing_parameters).await?;
-            let bridge_hub_call = send_xcm_bridge_hub(
-                &context,
-                vec![
-                    commands::set_gateway_address(&params.gateway_address),
-                    set_pricing_parameters,
-                    commands::gateway_operating_mode(
-                        &params.gateway_operating_mode.gateway_operating_mode,
-                    ),
-                    commands::force_checkpoint(&params.force_checkpoint),
-                ],
-            )
-            .await?;
-            let (register_ether_call, set_ether_metadata_call) =
-                commands::register_ether(&params.register_ether);
-            let asset_hub_call = send_xcm_asset_hub(
-                &context,
-                vec![
-                    register_ether_call,
-                    set_ether_metadata_call,
-                    force_xcm_version(),
-                    set_ethereum_fee,
-                ],
-            )
-            .await?;
-            utility_force_batch(vec![bridge_hub_call, asset_hub_call])
-        }
-        Command::UpdateAsset(params) => {
-            send_xcm_asset_hub(
-                &context,
-                vec![
-                    commands::make_asset_sufficient(params),
-                    commands::force_set_metadata(params),
-                ],
-            )
-            .await?
-        }
-        Command::GatewayOperatingMode(params) => {
-            let call = commands::gateway_operating_mode(&params.gateway_operating_mode);
-            send_xcm_bridge_hub(&context, vec![call]).await?
-        }
-        Command::Upgrade(params) => {
-            let call = commands::upgrade(params);
-            send_xcm_bridge_hub(&context, vec![call]).await?
-        }
-        Command::PricingParameters(params) => {
-            let (set_pricing_parameters, set_ethereum_fee) =
-                commands::pricing_parameters(&context, params).await?;
-            let bridge_hub_call =
-                send_xcm_bridge_hub(&context, vec![set_pricing_parameters]).await?;
-            let asset_hub_call = send_xcm_asset_hub(&context, vec![set_ethereum_fee]).await?;
-            utility_force_batch(vec![bridge_hub_call, asset_hub_call])
-        }
-        Command::HaltBridge(params) => {
-            let mut bh_calls = vec![];
-            let mut ah_calls = vec![];
-            let mut halt_all = params.all;
-            // if no individual option specified, assume halt the whole bridge.
-            if !params.gateway
-                && !params.inbound_queue
-                && !params.outbound_queue
-                && !params.ethereum_client
-                && !params.assethub_max_fee
-            {
-                halt_all = true;
-            }
-            if params.gateway || halt_all {
-                bh_calls.push(commands::gateway_operating_mode(
-                    &GatewayOperatingModeEnum::RejectingOutboundMessages,
-                ));
-            }
-            if params.inbound_queue || halt_all {
-                bh_calls.push(commands::inbound_queue_operating_mode(
-                    &OperatingModeEnum::Halted,
-                ));
-            }
-            if params.outbound_queue || halt_all {
-                bh_calls.push(commands::outbound_queue_operating_mode(
-                    &OperatingModeEnum::Halted,
-                ));
-            }
-            if params.ethereum_client || halt_all {
-                bh_calls.push(commands::ethereum_client_operating_mode(
-                    &OperatingModeEnum::Halted,
-                ));
-            }
-            if params.assethub_max_fee || halt_all {
-                ah_calls.push(commands::set_assethub_fee(u128::MAX));
-            }
-            if bh_calls.len() > 0 && ah_calls.len() == 0 {
-                send_xcm_bridge_hub(&context, bh_calls).await?
-            } else if ah_calls.len() > 0 && bh_calls.len() == 0 {
-                send_xcm_asset_hub(&context, ah_calls).await?
-            } else {
-                let call1 = send_xcm_bridge_hub(&context, bh_calls).await?;
-                let call2 = send_xcm_asset_hub(&context, ah_calls).await?;
-                utility_force_batch(vec![call1, call2])
+/// Preimage built for a command, with the call it dispatches once enacted
+struct Proposal {
+    call: Vec<u8>,
+    schedule: Option<Schedule>,
+    preimage: Vec<u8>,
+}
+
+// Build the call for the command, and the preimage scheduling it or wrapping it in a sudo call
+async fn build(cli: &Cli, context: &Context) -> Result<Proposal, Box<dyn std::error::Error>> {
+    let network = cli.network;
+    let call: Vec<u8> = with_network!(network, |runtime| {
+        runtime::proposal::call(context, &cli.command).await
+    })?;
+
+    let schedule = schedule::resolve(&cli.schedule, context).await?;
+    let preimage: Vec<u8> = with_network!(network, |runtime| {
+        runtime::proposal::preimage(&call, schedule, cli.sudo)
+    })?;
+
+    Ok(Proposal {
+        call,
+        schedule,
+        preimage,
+    })
+}
+
+// Decoding only needs the bundled metadata, so is done without connecting to the chains
+fn decoder(network: Network) -> Result<decode::Decoder, Box<dyn std::error::Error>> {
+    with_network!(network, |runtime| {
+        decode::Decoder::new(
+            network,
+            runtime::relay_runtime::METADATA,
+            runtime::bridge_hub_runtime::METADATA,
+            runtime::asset_hub_runtime::METADATA,
+        )
+    })
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = run().await {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    let network = cli.network;
+    if cli.sudo && !network.has_sudo() {
+        return Err(format!("Sudo is not available on {network}").into());
+    }
+
+    if let Command::Decode(params) = &cli.command {
+        let preimage = match &params.file {
+            Some(path) => decode::read_preimage(path)?,
+            None => params.preimage.clone().unwrap_or_default().to_vec(),
+        };
+        print!("{}", decoder(network)?.decode(&preimage)?.tree());
+        return Ok(());
+    }
+
+    if let Command::Diff(params) = &cli.command {
+        let decoder = decoder(network)?;
+        let (a, b) = (
+            decode::read_preimage(&params.a)?,
+            decode::read_preimage(&params.b)?,
+        );
+        let changes = diff::diff(&decoder.decode(&a)?, &decoder.decode(&b)?);
+        if params.json {
+            let output = serde_json::json!({
+                "a": format!("0x{}", hex::encode(blake2_256(&a))),
+                "b": format!("0x{}", hex::encode(blake2_256(&b))),
+                "changes": changes,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            eprintln!("A: 0x{} ({} bytes)", hex::encode(blake2_256(&a)), a.len());
+            eprintln!("B: 0x{} ({} bytes)", hex::encode(blake2_256(&b)), b.len());
+            if changes.is_empty() {
+                eprintln!("No differences");
             }
+            let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            print!("{}", diff::text(&changes, color));
         }
-        Command::RegisterEther(params) => {
-            let (register_ether_call, set_ether_metadata_call) = commands::register_ether(&params);
-            send_xcm_asset_hub(&context, vec![register_ether_call, set_ether_metadata_call]).await?
-        }
-        Command::TreasuryProposal2024(params) => treasury_commands::treasury_proposal(&params),
-        Command::GovUpdate202501(GovUpdate202501Args {
-            pricing_parameters,
-            register_ether,
-        }) => {
-            let (set_pricing_parameters, set_ethereum_fee) =
-                commands::pricing_parameters(&context, pricing_parameters).await?;
-
-            let bh_set_pricing_call =
-                send_xcm_bridge_hub(&context, vec![set_pricing_parameters]).await?;
-
-            let ah_set_pricing_call = send_xcm_asset_hub(&context, vec![set_ethereum_fee]).await?;
-
-            let (register_ether_call, set_ether_metadata_call) =
-                commands::register_ether(&register_ether);
-            let ah_register_ether_call =
-                send_xcm_asset_hub(&context, vec![register_ether_call, set_ether_metadata_call])
-                    .await?;
-
-            utility_force_batch(vec![
-                bh_set_pricing_call,
-                ah_set_pricing_call,
-                ah_register_ether_call,
-            ])
-        }
-        Command::RegisterPnaBatch202503 => {
-            #[cfg(not(feature = "polkadot"))]
-            panic!("RegisterPnaBatch202503 only for polkadot runtime.");
-
-            #[cfg(feature = "polkadot")]
-            {
-                let reg_call =
-                    send_xcm_bridge_hub(&context, commands::token_registrations()).await?;
-                reg_call
-            }
+        return Ok(());
+    }
+
+    if let Command::Verify(params) = &cli.command {
+        return verify::verify(&cli, params).await;
+    }
+
+    let context = connect(network, &cli.api_endpoints, &cli.pinned_blocks).await?;
+
+    if let Command::VerifyCheckpoint(params) = &cli.command {
+        return with_snowbridge!(network, |runtime| {
+            let checkpoint = runtime::snowbridge::load_checkpoint(&context, params).await?;
+            runtime::snowbridge::verify_checkpoint(&context, params, &checkpoint).await
+        });
+    }
+
+    if let Command::Status(params) = &cli.command {
+        return with_snowbridge!(network, |runtime| {
+            runtime::snowbridge::status(&context, params).await
+        });
+    }
+
+    if let Command::Assets(params) = &cli.command {
+        return with_network!(network, |runtime| {
+            runtime::assets::inventory(&context, params).await
+        });
+    }
+
+    if let Command::QuoteFees(params) = &cli.command {
+        return with_snowbridge!(network, |runtime| {
+            runtime::fees::quote_fees(&context, params).await
+        });
+    }
+
+    if let Command::SuggestPricing(params) = &cli.command {
+        return with_snowbridge!(network, |runtime| {
+            runtime::pricing::suggest_pricing(&context, params).await
+        });
+    }
+
+    if let Command::Plan(params) = &cli.command {
+        if !params.emit {
+            return with_snowbridge!(network, |runtime| {
+                runtime::plan::plan(&context, params).await.map(|_| ())
+            });
         }
-    };
+    }
 
-    #[cfg(any(feature = "westend", feature = "paseo"))]
-    let final_call = if cli.sudo { sudo(Box::new(call)) } else { call };
-    #[cfg(not(any(feature = "westend", feature = "paseo")))]
-    let final_call = call;
+    let Proposal {
+        call,
+        schedule,
+        preimage,
+    } = build(&cli, &context).await?;
 
-    let preimage = final_call.encode();
+    // The call is dry-run as it is dispatched once enacted, rather than its sudo or Scheduler
+    // wrapper, which would only check that the call can be scheduled
+    if cli.dry_run && !dry_run::dry_run(&context, &decoder(network)?, &call).await? {
+        return Err("Dry run failed".into());
+    }
 
-    generate_chopsticks_script(&preimage, "chopsticks-execute-upgrade.js".into())?;
+    generate_chopsticks_script(&preimage, schedule, "chopsticks-execute-upgrade.js".into())?;
 
     eprintln!("Preimage Hash: 0x{}", hex::encode(blake2_256(&preimage)));
     eprintln!("Preimage Size: {}", preimage.len());
 
-    match cli.format {
-        Format::Hex => {
+    let submission = match cli.opengov {
+        Some(OpenGovTrack::WhitelistedCaller) => Some(with_network!(network, |runtime| {
+            runtime::proposal::whitelisted_caller(&context, &preimage, cli.enactment_after).await
+        })?),
+        None => None,
+    };
+
+    match (cli.format, submission) {
+        (Format::Json, submission) => {
+            let manifest = provenance::manifest(
+                &context,
+                &decoder(network)?,
+                network,
+                &preimage,
+                submission.as_deref(),
+            )
+            .await?;
+            println!("{}", serde_json::to_string_pretty(&manifest)?);
+        }
+        (_, Some(submission)) => {
+            print!("{}", opengov::text(&submission));
+        }
+        (Format::Hex, None) => {
             println!("0x{}", hex::encode(preimage));
         }
-        Format::Binary => {
+        (Format::Binary, None) => {
             std::io::stdout().write_all(&preimage)?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_native_amounts_with_the_decimals_of_the_network() {
+        let amount = parse_native_amount("1.5").unwrap();
+        assert_eq!(amount.planck(Network::Polkadot), Ok(15_000_000_000));
+        assert_eq!(amount.planck(Network::Paseo), Ok(15_000_000_000));
+        assert_eq!(amount.planck(Network::Kusama), Ok(1_500_000_000_000));
+        assert_eq!(amount.planck(Network::Westend), Ok(1_500_000_000_000));
+
+        let amount = parse_native_amount("0.000000000001").unwrap();
+        assert_eq!(amount.planck(Network::Kusama), Ok(1));
+        assert!(amount.planck(Network::Polkadot).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_native_amounts() {
+        assert!(parse_native_amount("0.0000000000001").is_err());
+        assert!(parse_native_amount("1,5").is_err());
+        assert!(parse_native_amount("DOT").is_err());
+
+        let amount = parse_native_amount("1000000000000000000000000000000").unwrap();
+        assert!(amount.planck(Network::Kusama).is_err());
+        assert!(amount.planck(Network::Polkadot).is_err());
+    }
+}
@@ -0,0 +1,306 @@
+use serde::{de::DeserializeOwned, Deserialize};
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    steps: Vec<toml::Table>,
+}
+
+/// A step of a proposal, as command line arguments for the subcommand it runs
+#[derive(Debug)]
+pub struct Step {
+    pub description: Option<String>,
+    pub args: Vec<String>,
+}
+
+/// Load the steps of a proposal manifest. Each step names its subcommand with `command`, and
+/// passes its arguments as keys named like the command line options. Boolean options are
+/// passed when `true`, arrays repeat the option, and tables are passed as JSON. Occurrences of
+/// `${NAME}` in strings are replaced with the given variables, and relative paths of files are
+/// resolved against the directory of the manifest.
+pub fn load_steps(
+    path: &Path,
+    vars: &[(String, String)],
+) -> Result<Vec<Step>, Box<dyn std::error::Error>> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let manifest: Manifest =
+        toml::from_str(&data).map_err(|e| format!("Invalid manifest {}: {e}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+
+    manifest
+        .steps
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut table)| {
+            substitute_table(&mut table, vars)
+                .and_then(|()| {
+                    resolve_files(&mut table, dir);
+                    step(&mut table)
+                })
+                .map_err(|e| format!("Invalid step {}: {e}", index + 1).into())
+        })
+        .collect()
+}
+
+// Options taking a path to a file
+const FILE_OPTIONS: &[&str] = &["assets", "checkpoint", "initializer-abi", "then", "wasm"];
+
+// Replace `${NAME}` with the given variables in the strings of a value
+fn substitute(value: &mut toml::Value, vars: &[(String, String)]) -> Result<(), String> {
+    match value {
+        toml::Value::String(data) => {
+            for (name, value) in vars {
+                *data = data.replace(&format!("${{{name}}}"), value);
+            }
+            if let Some(start) = data.find("${") {
+                let end = data[start..]
+                    .find('}')
+                    .map_or(data.len(), |end| start + end + 1);
+                return Err(format!("Undefined variable {}", &data[start..end]));
+            }
+        }
+        toml::Value::Array(values) => {
+            for value in values {
+                substitute(value, vars)?;
+            }
+        }
+        toml::Value::Table(table) => substitute_table(table, vars)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn substitute_table(table: &mut toml::Table, vars: &[(String, String)]) -> Result<(), String> {
+    for (_, value) in table.iter_mut() {
+        substitute(value, vars)?;
+    }
+    Ok(())
+}
+
+// Resolve the relative paths given to file options against the directory of the manifest
+fn resolve_files(table: &mut toml::Table, dir: &Path) {
+    for key in FILE_OPTIONS {
+        let values = match table.get_mut(*key) {
+            Some(toml::Value::Array(values)) => values.iter_mut().collect(),
+            Some(value) => vec![value],
+            None => vec![],
+        };
+        for value in values {
+            if let toml::Value::String(file) = value {
+                *file = dir.join(&*file).to_string_lossy().into_owned();
+            }
+        }
+    }
+}
+
+fn step(table: &mut toml::Table) -> Result<Step, String> {
+    let command = match table.remove("command") {
+        Some(toml::Value::String(command)) => command,
+        _ => return Err("expected a `command` string".into()),
+    };
+    let description = match table.remove("description") {
+        Some(toml::Value::String(description)) => Some(description),
+        None => None,
+        _ => return Err("expected `description` to be a string".into()),
+    };
+
+    let mut args = vec![command];
+    args.extend(options(table)?);
+
+    Ok(Step { description, args })
+}
+
+// Command line options for the keys of a table
+fn options(table: &toml::Table) -> Result<Vec<String>, String> {
+    let mut args = vec![];
+    for (key, value) in table.iter() {
+        let values = match value {
+            toml::Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            match value {
+                toml::Value::Boolean(true) => args.push(format!("--{key}")),
+                toml::Value::Boolean(false) => {}
+                toml::Value::String(value) => args.push(format!("--{key}={value}")),
+                toml::Value::Table(_) | toml::Value::Array(_) => {
+                    let json: serde_json::Value = value
+                        .clone()
+                        .try_into()
+                        .map_err(|e| format!("{key}: {e}"))?;
+                    args.push(format!("--{key}={json}"));
+                }
+                value => args.push(format!("--{key}={value}")),
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// Load a TOML file of options, keyed like the command line options as in a manifest step
+pub fn load_options(path: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let table: toml::Table =
+        toml::from_str(&data).map_err(|e| format!("Invalid {}: {e}", path.display()))?;
+    Ok(options(&table).map_err(|e| format!("Invalid {}: {e}", path.display()))?)
+}
+
+/// Load a data file, such as a list of assets, from JSON or from TOML given a `.toml` extension
+pub fn load_data<T: DeserializeOwned>(path: &Path) -> Result<T, Box<dyn std::error::Error>> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    // TOML is converted to JSON first, as the TOML deserializer does not support u128
+    let value = match path.extension() {
+        Some(ext) if ext == "toml" => {
+            serde_json::from_value(toml::from_str::<serde_json::Value>(&data)?)?
+        }
+        _ => serde_json::from_str(&data)?,
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // Write a manifest to a directory of its own, returning its path
+    fn manifest(name: &str, data: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("snowbridge-preimage-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("proposal.toml");
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn maps_keys_to_options() {
+        let table: toml::Table = toml::from_str(
+            r#"
+            force = true
+            dry-run = false
+            para-id = 1000
+            then = ["a.hex", "b.hex"]
+            location = { parents = 1, interior = "Here" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            options(&table).unwrap(),
+            [
+                "--force",
+                r#"--location={"interior":"Here","parents":1}"#,
+                "--para-id=1000",
+                "--then=a.hex",
+                "--then=b.hex",
+            ]
+        );
+    }
+
+    #[test]
+    fn loads_steps() {
+        let path = manifest(
+            "steps",
+            r#"
+            [[steps]]
+            command = "halt-bridge"
+            description = "Halt the bridge"
+            all = true
+
+            [[steps]]
+            command = "register-erc20"
+            assets = "tokens.toml"
+            "#,
+        );
+        let steps = load_steps(&path, &[]).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].description.as_deref(), Some("Halt the bridge"));
+        assert_eq!(steps[0].args, ["halt-bridge", "--all"]);
+        assert_eq!(steps[1].description, None);
+        let tokens = path.parent().unwrap().join("tokens.toml");
+        assert_eq!(
+            steps[1].args,
+            [
+                "register-erc20".into(),
+                format!("--assets={}", tokens.display())
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_files_against_the_manifest() {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/register-kusama-assets.toml");
+        let steps = load_steps(&path, &[]).unwrap();
+        let assets = steps[1].args[1].strip_prefix("--assets=").unwrap();
+        assert!(Path::new(assets).is_file(), "{assets}");
+
+        let mut table: toml::Table = toml::from_str(
+            r#"wasm = "/runtime.wasm"
+then = ["a.hex", "/b.hex"]
+name = "c.hex""#,
+        )
+        .unwrap();
+        resolve_files(&mut table, Path::new("proposals"));
+        assert_eq!(table["wasm"].as_str(), Some("/runtime.wasm"));
+        assert_eq!(
+            table["then"],
+            toml::Value::Array(vec!["proposals/a.hex".into(), "/b.hex".into()])
+        );
+        assert_eq!(table["name"].as_str(), Some("c.hex"));
+    }
+
+    #[test]
+    fn substitutes_variables_in_strings() {
+        let path = manifest(
+            "vars",
+            r#"
+            # Comments may mention ${UNDEFINED}
+            [[steps]]
+            command = "call"
+            args = '{ "who": "${beneficiary}", "note": "${beneficiary} ${amount}" }'
+            amount = ["${amount}"]
+            location = { id = "${beneficiary}" }
+            "#,
+        );
+        let vars = [
+            ("beneficiary".to_string(), "0x01".to_string()),
+            // Values are substituted as they are, and not parsed as TOML
+            ("amount".to_string(), "\"1\"\n[[steps]]".to_string()),
+        ];
+        let steps = load_steps(&path, &vars).unwrap();
+        assert_eq!(
+            steps[0].args,
+            [
+                "call",
+                "--amount=\"1\"\n[[steps]]",
+                "--args={ \"who\": \"0x01\", \"note\": \"0x01 \"1\"\n[[steps]]\" }",
+                r#"--location={"id":"0x01"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_undefined_variables() {
+        let path = manifest(
+            "undefined",
+            r#"
+            [[steps]]
+            command = "call"
+            args = { a = "${beneficiary}", b = "${amount" }
+            "#,
+        );
+        let vars = [("beneficiary".to_string(), "0x01".to_string())];
+        assert_eq!(
+            load_steps(&path, &vars).unwrap_err().to_string(),
+            "Invalid step 1: Undefined variable ${amount"
+        );
+        assert_eq!(
+            load_steps(&path, &[]).unwrap_err().to_string(),
+            "Invalid step 1: Undefined variable ${beneficiary}"
+        );
+    }
+}
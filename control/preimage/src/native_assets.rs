@@ -0,0 +1,194 @@
+use super::assets::{ensure_unique, NativeAsset};
+use super::bridge_hub_runtime;
+use super::bridge_hub_runtime::runtime_types::snowbridge_core::outbound::v1::Command as OutboundCommand;
+use super::bridge_hub_runtime::RuntimeCall as BridgeHubRuntimeCall;
+use super::snowbridge::bridge_hub_location;
+use super::NETWORK;
+use crate::{manifest, Context, RegisterPnaArgs};
+use codec::{Decode, Encode};
+use serde::Deserialize;
+use subxt::{utils::H160, utils::H256, OnlineClient, PolkadotConfig};
+
+/// Maximum length of the name and symbol in `AssetMetadata`
+const METADATA_FIELD_MAX_LEN: usize = 32;
+
+#[derive(Debug, Deserialize)]
+struct PolkadotNativeAssets {
+    assets: Vec<PolkadotNativeAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolkadotNativeAsset {
+    location: xcm::v4::Location,
+    name: String,
+    symbol: String,
+    decimals: u8,
+}
+
+pub async fn register_polkadot_native_assets(
+    context: &Context,
+    params: &RegisterPnaArgs,
+) -> Result<Vec<BridgeHubRuntimeCall>, Box<dyn std::error::Error>> {
+    let manifest: PolkadotNativeAssets = manifest::load_data(&params.assets)?;
+    let locations: Vec<_> = manifest
+        .assets
+        .iter()
+        .map(|asset| &asset.location)
+        .collect();
+    ensure_unique(&locations)?;
+
+    eprintln!("Polkadot-native assets:");
+    let mut calls = vec![];
+    for (index, asset) in manifest.assets.iter().enumerate() {
+        let call = register_polkadot_native_asset(context, asset)
+            .await
+            .map_err(|e| format!("Invalid asset #{index} ({}): {e}", asset.symbol))?;
+        eprintln!(
+            "  {}: {}, {} decimals, {:?}",
+            asset.symbol, asset.name, asset.decimals, asset.location
+        );
+        calls.push(call);
+    }
+
+    Ok(calls)
+}
+
+async fn register_polkadot_native_asset(
+    context: &Context,
+    asset: &PolkadotNativeAsset,
+) -> Result<BridgeHubRuntimeCall, Box<dyn std::error::Error>> {
+    use super::bridge_hub_runtime::runtime_types::{
+        bounded_collections, snowbridge_core, snowbridge_pallet_system,
+    };
+
+    if asset.name.len() > METADATA_FIELD_MAX_LEN {
+        return Err(format!("name is longer than {METADATA_FIELD_MAX_LEN} bytes").into());
+    }
+    if asset.symbol.len() > METADATA_FIELD_MAX_LEN {
+        return Err(format!("symbol is longer than {METADATA_FIELD_MAX_LEN} bytes").into());
+    }
+
+    let location = bridge_hub_location(&asset.location)?;
+
+    // Registering the token again would fail on Ethereum
+    if let Some(token_id) = query_native_token_id(
+        &context.bridge_hub_api,
+        context.bridge_hub_block.hash,
+        &ethereum_location(&asset.location)?,
+    )
+    .await?
+    {
+        return Err(format!("already registered with token ID {token_id:?}").into());
+    }
+
+    Ok(BridgeHubRuntimeCall::EthereumSystem(
+        snowbridge_pallet_system::pallet::Call::register_token {
+            location: Box::new(location),
+            metadata: snowbridge_core::AssetMetadata {
+                name: bounded_collections::bounded_vec::BoundedVec(asset.name.as_bytes().to_vec()),
+                symbol: bounded_collections::bounded_vec::BoundedVec(
+                    asset.symbol.as_bytes().to_vec(),
+                ),
+                decimals: asset.decimals,
+            },
+        },
+    ))
+}
+
+// Reanchor a location relative to BridgeHub to Ethereum, as BridgeHub registers tokens by the
+// location seen from Ethereum
+fn ethereum_location(location: &xcm::v4::Location) -> Result<xcm::v4::Location, String> {
+    use xcm::v4::{InteriorLocation, Junction, NetworkId, Reanchorable};
+
+    let ethereum = xcm::v4::Location::new(
+        2,
+        [Junction::GlobalConsensus(NetworkId::Ethereum {
+            chain_id: NETWORK.chain_id(),
+        })],
+    );
+    let bridge_hub: InteriorLocation = [
+        Junction::GlobalConsensus(NETWORK.relay_network()),
+        Junction::Parachain(NETWORK.bridge_hub_id()),
+    ]
+    .into();
+    location
+        .clone()
+        .reanchored(&ethereum, &bridge_hub)
+        .map_err(|_| "location cannot be reanchored to Ethereum".to_owned())
+}
+
+// Polkadot-native assets registered on Ethereum
+pub async fn polkadot_native_assets(
+    context: &Context,
+) -> Result<Vec<NativeAsset>, Box<dyn std::error::Error>> {
+    let tokens =
+        query_native_tokens(&context.bridge_hub_api, context.bridge_hub_block.hash).await?;
+    Ok(tokens
+        .into_iter()
+        .map(|(token_id, location)| NativeAsset { token_id, location })
+        .collect())
+}
+
+// Polkadot-native assets registered on Ethereum, by token ID, with their locations reanchored
+// to Ethereum
+pub async fn query_native_tokens(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+) -> Result<Vec<(H256, xcm::v4::Location)>, Box<dyn std::error::Error>> {
+    let storage_query = bridge_hub_runtime::storage()
+        .ethereum_system()
+        .foreign_to_native_id_iter();
+    let mut entries = api.storage().at(at).iter(storage_query).await?;
+
+    let mut tokens = vec![];
+    while let Some(kv) = entries.next().await {
+        let kv = kv?;
+        // The token ID ends the key, after its Blake2_128Concat hash
+        let token_id = H256::decode(&mut &kv.key_bytes[kv.key_bytes.len() - 32..])?;
+        let location = xcm::v4::Location::decode(&mut kv.value.encode().as_slice())?;
+        tokens.push((token_id, location));
+    }
+
+    Ok(tokens)
+}
+
+// ID of the token registered on Ethereum for a location reanchored to Ethereum, if any
+pub async fn query_native_token_id(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+    location: &xcm::v4::Location,
+) -> Result<Option<H256>, Box<dyn std::error::Error>> {
+    // The key is a v4 or v5 location depending on the runtime, which encode the same way
+    let location =
+        bridge_hub_runtime::ethereum_system::storage::types::native_to_foreign_id::Param0::decode(
+            &mut location.encode().as_slice(),
+        )?;
+    let storage_query = bridge_hub_runtime::storage()
+        .ethereum_system()
+        .native_to_foreign_id(location);
+
+    Ok(api.storage().at(at).fetch(&storage_query).await?)
+}
+
+// Messages sent to Ethereum for Polkadot-native tokens, quoted by `quote-fees`
+pub fn outbound_commands() -> Vec<(&'static str, OutboundCommand)> {
+    vec![
+        (
+            "Register token",
+            OutboundCommand::RegisterForeignToken {
+                token_id: H256::zero(),
+                name: vec![],
+                symbol: vec![],
+                decimals: 0,
+            },
+        ),
+        (
+            "Transfer Polkadot-native token",
+            OutboundCommand::MintForeignToken {
+                token_id: H256::zero(),
+                recipient: H160::zero(),
+                amount: 0,
+            },
+        ),
+    ]
+}
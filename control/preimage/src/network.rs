@@ -0,0 +1,208 @@
+// The modules shared by the networks are included by each of them, and compiled against the
+// runtimes of that network
+#![allow(clippy::duplicate_mod)]
+
+pub mod kusama;
+pub mod paseo;
+pub mod polkadot;
+pub mod westend;
+
+use clap::ValueEnum;
+use snowbridge_router_primitives::inbound::GlobalConsensusEthereumConvertsFor;
+use xcm::v4::{InteriorLocation, Junction::*, Location, NetworkId};
+use xcm_builder::GlobalConsensusParachainConvertsFor;
+use xcm_executor::traits::ConvertLocation;
+
+/// Relay chain network a proposal is built for. Each network has a module of the same name,
+/// compiling the proposal code against its generated runtime types.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum Network {
+    Polkadot,
+    Kusama,
+    Westend,
+    Paseo,
+}
+
+impl Network {
+    /// Symbol of the relay chain token
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Network::Polkadot => "DOT",
+            Network::Kusama => "KSM",
+            Network::Westend => "WND",
+            Network::Paseo => "PAS",
+        }
+    }
+
+    /// Number of decimal places of the relay chain token
+    pub fn decimals(self) -> u8 {
+        match self {
+            Network::Polkadot | Network::Paseo => 10,
+            Network::Kusama | Network::Westend => 12,
+        }
+    }
+
+    /// Address format of accounts on the relay chain and its system parachains
+    pub fn ss58_prefix(self) -> u16 {
+        match self {
+            Network::Polkadot | Network::Paseo => 0,
+            Network::Kusama => 2,
+            Network::Westend => 42,
+        }
+    }
+
+    /// Consensus system of the relay chain in XCM locations. Paseo is configured as Polkadot.
+    pub fn relay_network(self) -> NetworkId {
+        match self {
+            Network::Polkadot | Network::Paseo => NetworkId::Polkadot,
+            Network::Kusama => NetworkId::Kusama,
+            Network::Westend => NetworkId::Westend,
+        }
+    }
+
+    pub fn asset_hub_id(self) -> u32 {
+        1000
+    }
+
+    pub fn bridge_hub_id(self) -> u32 {
+        1002
+    }
+
+    /// Chain ID of the Ethereum network bridged to
+    pub fn chain_id(self) -> u64 {
+        match self {
+            Network::Polkadot | Network::Kusama => 1,
+            Network::Westend | Network::Paseo => 11155111,
+        }
+    }
+
+    /// XCM version of the messages sent on the network: AssetHub is told to use it for the
+    /// Ethereum network, and dry runs convert the messages forwarded by the relay chain to it
+    pub fn xcm_version(self) -> u32 {
+        4
+    }
+
+    /// Whether the relay chain has a sudo key, so that proposals can be wrapped in `Sudo.sudo`
+    pub fn has_sudo(self) -> bool {
+        matches!(self, Network::Westend | Network::Paseo)
+    }
+
+    /// Whether the Fellowship votes on the relay chain, rather than on the Collectives chain
+    pub fn fellowship_on_relay(self) -> bool {
+        matches!(self, Network::Kusama)
+    }
+
+    /// Owner of the assets bridged from Ethereum to AssetHub. On Kusama they arrive over the
+    /// Polkadot<>Kusama bridge, and are owned by the sovereign account of Polkadot AssetHub.
+    pub fn asset_owner(self) -> [u8; 32] {
+        match self {
+            Network::Kusama => polkadot_asset_hub_sovereign_on_kusama(),
+            _ => GlobalConsensusEthereumConvertsFor::<[u8; 32]>::from_chain_id(&self.chain_id()),
+        }
+    }
+
+    pub fn relay_api(self) -> &'static str {
+        match self {
+            Network::Polkadot => "wss://polkadot.api.onfinality.io/public-ws",
+            Network::Kusama => "wss://kusama-rpc.dwellir.com",
+            Network::Westend => "wss://westend-rpc.dwellir.com",
+            Network::Paseo => "wss://paseo-rpc.dwellir.com",
+        }
+    }
+
+    pub fn bridge_hub_api(self) -> &'static str {
+        match self {
+            Network::Polkadot => "wss://polkadot-bridge-hub-rpc.polkadot.io",
+            Network::Kusama => "wss://bridge-hub-kusama-rpc.dwellir.com",
+            Network::Westend => "wss://bridge-hub-westend-rpc.dwellir.com",
+            Network::Paseo => "wss://bridge-hub-paseo.dotters.network",
+        }
+    }
+
+    pub fn asset_hub_api(self) -> &'static str {
+        match self {
+            Network::Polkadot => "wss://polkadot-asset-hub-rpc.polkadot.io",
+            Network::Kusama => "wss://asset-hub-kusama-rpc.dwellir.com",
+            Network::Westend => "wss://asset-hub-westend-rpc.dwellir.com",
+            Network::Paseo => "wss://asset-hub-paseo-rpc.dwellir.com",
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Network::Polkadot => "Polkadot",
+            Network::Kusama => "Kusama",
+            Network::Westend => "Westend",
+            Network::Paseo => "Paseo",
+        };
+        f.write_str(name)
+    }
+}
+
+fn polkadot_asset_hub_sovereign_on_kusama() -> [u8; 32] {
+    frame_support::parameter_types! {
+        pub UniversalLocation: InteriorLocation =
+            [GlobalConsensus(NetworkId::Kusama), Parachain(1000)].into();
+    }
+    let polkadot_asset_hub = Location {
+        parents: 2,
+        interior: [GlobalConsensus(NetworkId::Polkadot), Parachain(1000)].into(),
+    };
+    GlobalConsensusParachainConvertsFor::<UniversalLocation, [u8; 32]>::convert_location(
+        &polkadot_asset_hub,
+    )
+    .expect("Polkadot AssetHub is a remote parachain")
+}
+
+/// Evaluate `$body` with `$runtime` bound to the module of the given network. The body must
+/// evaluate to a `Result` with a boxed error.
+macro_rules! with_network {
+    ($network:expr, |$runtime:ident| $body:expr) => {
+        match $network {
+            $crate::network::Network::Polkadot => {
+                use $crate::network::polkadot as $runtime;
+                $body
+            }
+            $crate::network::Network::Kusama => {
+                use $crate::network::kusama as $runtime;
+                $body
+            }
+            $crate::network::Network::Westend => {
+                use $crate::network::westend as $runtime;
+                $body
+            }
+            $crate::network::Network::Paseo => {
+                use $crate::network::paseo as $runtime;
+                $body
+            }
+        }
+    };
+}
+
+/// Like `with_network`, for code using the Snowbridge pallets on BridgeHub, which fails on
+/// networks without them.
+macro_rules! with_snowbridge {
+    ($network:expr, |$runtime:ident| $body:expr) => {
+        match $network {
+            $crate::network::Network::Polkadot => {
+                use $crate::network::polkadot as $runtime;
+                $body
+            }
+            $crate::network::Network::Westend => {
+                use $crate::network::westend as $runtime;
+                $body
+            }
+            $crate::network::Network::Paseo => {
+                use $crate::network::paseo as $runtime;
+                $body
+            }
+            network @ $crate::network::Network::Kusama => {
+                Err(format!("Snowbridge is not deployed on {network} BridgeHub").into())
+            }
+        }
+    };
+}
+
+pub(crate) use {with_network, with_snowbridge};
@@ -0,0 +1,85 @@
+use super::Network;
+
+pub const NETWORK: Network = Network::Kusama;
+
+pub mod relay_runtime {
+    pub use kusama_runtime::runtime_types::staging_kusama_runtime::RuntimeCall;
+    pub use kusama_runtime::*;
+}
+
+pub mod bridge_hub_runtime {
+    pub use bridge_hub_kusama_runtime::runtime_types::bridge_hub_kusama_runtime::RuntimeCall;
+    pub use bridge_hub_kusama_runtime::*;
+}
+
+pub mod asset_hub_runtime {
+    pub use asset_hub_kusama_runtime::runtime_types::asset_hub_kusama_runtime::RuntimeCall;
+    pub use asset_hub_kusama_runtime::*;
+}
+
+pub mod asset_hub_types {
+    pub use super::asset_hub_runtime::runtime_types::staging_xcm::v4::{
+        junction::Junction::AccountKey20,
+        junction::Junction::GlobalConsensus,
+        junction::NetworkId,
+        junctions::Junctions::{X1, X2},
+        location::Location,
+    };
+
+    pub fn get_ether_id(chain_id: u64) -> Location {
+        Location {
+            parents: 2,
+            interior: X1([GlobalConsensus(NetworkId::Ethereum { chain_id })]),
+        }
+    }
+    pub fn get_asset_id(chain_id: u64, key: [u8; 20]) -> Location {
+        Location {
+            parents: 2,
+            interior: X2([
+                GlobalConsensus(NetworkId::Ethereum { chain_id }),
+                AccountKey20 { network: None, key },
+            ]),
+        }
+    }
+}
+
+pub fn sudo(_call: Box<relay_runtime::RuntimeCall>) -> Option<relay_runtime::RuntimeCall> {
+    None
+}
+
+// Snowbridge is not deployed on Kusama BridgeHub. Ethereum assets reach Kusama AssetHub over
+// the Polkadot<>Kusama bridge, so only the commands for AssetHub and runtime upgrades apply.
+pub mod snowbridge {
+    use super::helpers::Calls;
+    use crate::{Command, Context};
+
+    pub async fn build_calls(
+        _context: &Context,
+        _command: &Command,
+    ) -> Result<Calls, Box<dyn std::error::Error>> {
+        Err("Snowbridge is not deployed on Kusama BridgeHub".into())
+    }
+}
+
+// No Polkadot-native assets are registered without the EthereumSystem pallet
+pub mod native_assets {
+    use super::assets::NativeAsset;
+    use crate::Context;
+
+    pub async fn polkadot_native_assets(
+        _context: &Context,
+    ) -> Result<Vec<NativeAsset>, Box<dyn std::error::Error>> {
+        Ok(vec![])
+    }
+}
+
+#[path = "../assets.rs"]
+pub mod assets;
+#[path = "../commands.rs"]
+pub mod commands;
+// The formatting of Snowbridge parameters is unused without the Snowbridge commands
+#[allow(dead_code)]
+#[path = "../helpers.rs"]
+pub mod helpers;
+#[path = "../proposal.rs"]
+pub mod proposal;
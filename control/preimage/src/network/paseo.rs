@@ -0,0 +1,107 @@
+use super::Network;
+
+pub const NETWORK: Network = Network::Paseo;
+
+pub mod relay_runtime {
+    pub use paseo_runtime::runtime_types::paseo_runtime::RuntimeCall;
+    pub use paseo_runtime::*;
+}
+
+pub mod bridge_hub_runtime {
+    pub use bridge_hub_paseo_runtime::runtime_types::bridge_hub_paseo_runtime::RuntimeCall;
+    pub use bridge_hub_paseo_runtime::*;
+}
+
+pub mod asset_hub_runtime {
+    pub use asset_hub_paseo_runtime::runtime_types::asset_hub_paseo_runtime::RuntimeCall;
+    pub use asset_hub_paseo_runtime::*;
+}
+
+pub mod asset_hub_types {
+    pub use super::asset_hub_runtime::runtime_types::staging_xcm::v3::multilocation::MultiLocation as Location;
+    pub use super::asset_hub_runtime::runtime_types::xcm::v3::{
+        junction::Junction::AccountKey20,
+        junction::Junction::GlobalConsensus,
+        junction::NetworkId,
+        junctions::Junctions::{X1, X2},
+    };
+
+    pub fn get_ether_id(chain_id: u64) -> Location {
+        Location {
+            parents: 2,
+            interior: X1(GlobalConsensus(NetworkId::Ethereum { chain_id })),
+        }
+    }
+    pub fn get_asset_id(chain_id: u64, key: [u8; 20]) -> Location {
+        Location {
+            parents: 2,
+            interior: X2(
+                GlobalConsensus(NetworkId::Ethereum { chain_id }),
+                AccountKey20 { network: None, key },
+            ),
+        }
+    }
+    // `PolkadotXcm.force_xcm_version` takes a current location rather than a v3 one
+    pub fn get_ethereum_location(
+        chain_id: u64,
+    ) -> super::asset_hub_runtime::runtime_types::staging_xcm::v4::location::Location {
+        use super::asset_hub_runtime::runtime_types::staging_xcm::v4::{
+            junction::Junction::GlobalConsensus, junction::NetworkId, junctions::Junctions::X1,
+            location::Location,
+        };
+        Location {
+            parents: 2,
+            interior: X1([GlobalConsensus(NetworkId::Ethereum { chain_id })]),
+        }
+    }
+}
+
+pub fn sudo(call: Box<relay_runtime::RuntimeCall>) -> Option<relay_runtime::RuntimeCall> {
+    Some(relay_runtime::RuntimeCall::Sudo(
+        relay_runtime::runtime_types::pallet_sudo::pallet::Call::sudo { call },
+    ))
+}
+
+// The EthereumSystem pallet on Paseo BridgeHub predates the registry of Polkadot-native tokens
+pub mod native_assets {
+    use super::assets::NativeAsset;
+    use super::bridge_hub_runtime::runtime_types::snowbridge_core::outbound::v1::Command as OutboundCommand;
+    use super::bridge_hub_runtime::RuntimeCall as BridgeHubRuntimeCall;
+    use crate::{Context, RegisterPnaArgs};
+
+    pub async fn register_polkadot_native_assets(
+        _context: &Context,
+        _params: &RegisterPnaArgs,
+    ) -> Result<Vec<BridgeHubRuntimeCall>, Box<dyn std::error::Error>> {
+        Err("Polkadot-native assets cannot be registered on Paseo BridgeHub".into())
+    }
+
+    pub async fn polkadot_native_assets(
+        _context: &Context,
+    ) -> Result<Vec<NativeAsset>, Box<dyn std::error::Error>> {
+        Ok(vec![])
+    }
+
+    pub fn outbound_commands() -> Vec<(&'static str, OutboundCommand)> {
+        vec![]
+    }
+}
+
+#[path = "../assets.rs"]
+pub mod assets;
+#[path = "../commands.rs"]
+pub mod commands;
+#[path = "../fees.rs"]
+pub mod fees;
+#[path = "../helpers.rs"]
+pub mod helpers;
+#[path = "../plan.rs"]
+pub mod plan;
+#[path = "../pricing.rs"]
+pub mod pricing;
+#[path = "../proposal.rs"]
+pub mod proposal;
+#[path = "../queries.rs"]
+pub mod queries;
+#[path = "../snowbridge.rs"]
+pub mod snowbridge;
@@ -0,0 +1,72 @@
+use super::Network;
+
+pub const NETWORK: Network = Network::Polkadot;
+
+pub mod relay_runtime {
+    pub use polkadot_runtime::runtime_types::polkadot_runtime::RuntimeCall;
+    pub use polkadot_runtime::*;
+}
+
+pub mod bridge_hub_runtime {
+    pub use bridge_hub_polkadot_runtime::runtime_types::bridge_hub_polkadot_runtime::RuntimeCall;
+    pub use bridge_hub_polkadot_runtime::*;
+}
+
+pub mod asset_hub_runtime {
+    pub use asset_hub_polkadot_runtime::runtime_types::asset_hub_polkadot_runtime::RuntimeCall;
+    pub use asset_hub_polkadot_runtime::*;
+}
+
+pub mod asset_hub_types {
+    pub use super::asset_hub_runtime::runtime_types::staging_xcm::v4::{
+        junction::Junction::AccountKey20,
+        junction::Junction::GlobalConsensus,
+        junction::NetworkId,
+        junctions::Junctions::{X1, X2},
+        location::Location,
+    };
+
+    pub fn get_ether_id(chain_id: u64) -> Location {
+        Location {
+            parents: 2,
+            interior: X1([GlobalConsensus(NetworkId::Ethereum { chain_id })]),
+        }
+    }
+    pub fn get_asset_id(chain_id: u64, key: [u8; 20]) -> Location {
+        Location {
+            parents: 2,
+            interior: X2([
+                GlobalConsensus(NetworkId::Ethereum { chain_id }),
+                AccountKey20 { network: None, key },
+            ]),
+        }
+    }
+    pub fn get_ethereum_location(chain_id: u64) -> Location {
+        get_ether_id(chain_id)
+    }
+}
+
+pub fn sudo(_call: Box<relay_runtime::RuntimeCall>) -> Option<relay_runtime::RuntimeCall> {
+    None
+}
+
+#[path = "../assets.rs"]
+pub mod assets;
+#[path = "../commands.rs"]
+pub mod commands;
+#[path = "../fees.rs"]
+pub mod fees;
+#[path = "../helpers.rs"]
+pub mod helpers;
+#[path = "../native_assets.rs"]
+pub mod native_assets;
+#[path = "../plan.rs"]
+pub mod plan;
+#[path = "../pricing.rs"]
+pub mod pricing;
+#[path = "../proposal.rs"]
+pub mod proposal;
+#[path = "../queries.rs"]
+pub mod queries;
+#[path = "../snowbridge.rs"]
+pub mod snowbridge;
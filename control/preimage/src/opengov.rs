@@ -0,0 +1,60 @@
+use clap::ValueEnum;
+use sp_crypto_hashing::blake2_256;
+
+/// OpenGov track to build the referendum submission for
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum OpenGovTrack {
+    WhitelistedCaller,
+}
+
+/// An encoded call or message making up a referendum submission
+pub struct Submission {
+    pub name: String,
+    /// Where and by whom the call is submitted
+    pub description: String,
+    pub encoded: Vec<u8>,
+}
+
+impl Submission {
+    pub fn new(name: &str, description: String, encoded: Vec<u8>) -> Self {
+        Submission {
+            name: name.into(),
+            description,
+            encoded,
+        }
+    }
+}
+
+/// Render the submission in the order the steps are taken, with the hash and length of each
+pub fn text(submission: &[Submission]) -> String {
+    submission
+        .iter()
+        .map(|item| {
+            format!(
+                "{}\n  {}\n  Hash: 0x{}\n  Length: {}\n  0x{}\n",
+                item.name,
+                item.description,
+                hex::encode(blake2_256(&item.encoded)),
+                item.encoded.len(),
+                hex::encode(&item.encoded),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render the submission as JSON, in the same order
+pub fn json(submission: &[Submission]) -> serde_json::Value {
+    submission
+        .iter()
+        .map(|item| {
+            serde_json::json!({
+                "name": item.name,
+                "description": item.description,
+                "hash": format!("0x{}", hex::encode(blake2_256(&item.encoded))),
+                "length": item.encoded.len(),
+                "encoded": format!("0x{}", hex::encode(&item.encoded)),
+            })
+        })
+        .collect()
+}
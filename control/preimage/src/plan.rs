@@ -0,0 +1,524 @@
+use super::asset_hub_runtime::foreign_assets::storage::types::{asset::Asset, metadata::Metadata};
+use super::asset_hub_runtime::runtime_types::pallet_assets::types::AssetStatus;
+use super::asset_hub_runtime::RuntimeCall as AssetHubRuntimeCall;
+use super::asset_hub_types::get_asset_id;
+use super::assets::query_foreign_asset;
+use super::bridge_hub_runtime::runtime_types::snowbridge_core::operating_mode::BasicOperatingMode;
+use super::bridge_hub_runtime::RuntimeCall as BridgeHubRuntimeCall;
+use super::commands::{self, Erc20Asset};
+use super::helpers::{ether, exchange_rate, fixed, gwei, native, Calls};
+use super::queries::{
+    query_assethub_base_fee, query_gateway_address, query_operating_modes, query_pricing_parameters,
+};
+use super::snowbridge::{
+    assethub_base_fee, build_pricing_parameters, ethereum_client_operating_mode,
+    gateway_operating_mode, inbound_queue_operating_mode, outbound_queue_operating_mode,
+    set_assethub_fee, set_gateway_address, set_pricing_parameters, token_transfer_fees,
+};
+use super::NETWORK;
+use crate::ethereum;
+use crate::provenance;
+use crate::{manifest, Context, DesiredState, OperatingModeEnum, PlanArgs, UpdateAssetArgs};
+use alloy_primitives::{Address, U256};
+use clap::Parser;
+
+// Call setting the operating mode of a bridge pallet
+type OperatingModeCall = fn(&OperatingModeEnum) -> BridgeHubRuntimeCall;
+
+// Differences from the desired state, by the chain holding the state
+#[derive(Default)]
+struct Changes {
+    bridge_hub: Vec<String>,
+    asset_hub: Vec<String>,
+    gateway: Vec<String>,
+}
+
+// Compare the desired state in a state file with BridgeHub, AssetHub and the Gateway, and print
+// the differences. With `--emit`, also build the calls changing only the values which differ.
+pub async fn plan(
+    context: &Context,
+    params: &PlanArgs,
+) -> Result<Calls, Box<dyn std::error::Error>> {
+    let state = DesiredState::try_parse_from(manifest::load_options(&params.state)?)
+        .map_err(|e| format!("Invalid state {}: {e}", params.state.display()))?;
+    let api = &context.bridge_hub_api;
+    let at = context.bridge_hub_block.hash;
+    let emit = params.emit;
+
+    let mut changes = Changes::default();
+    let mut calls = Calls::default();
+
+    let mut base_fee = state
+        .assethub_base_fee
+        .as_ref()
+        .map(|fee| fee.planck(NETWORK))
+        .transpose()?;
+
+    if let Some(args) = &state.pricing_parameters {
+        let desired = build_pricing_parameters(args)?;
+        let current = query_pricing_parameters(api, at).await?;
+        let list = &mut changes.bridge_hub;
+        let mut changed = change(
+            list,
+            "ExchangeRate",
+            exchange_rate(current.exchange_rate.0),
+            exchange_rate(desired.exchange_rate.0),
+        );
+        changed |= change(
+            list,
+            "Multiplier",
+            fixed(current.multiplier.0),
+            fixed(desired.multiplier.0),
+        );
+        changed |= change(
+            list,
+            "FeePerGas",
+            gwei(U256::from_limbs(current.fee_per_gas.0)),
+            gwei(U256::from_limbs(desired.fee_per_gas.0)),
+        );
+        changed |= change(
+            list,
+            "LocalReward",
+            native(current.rewards.local),
+            native(desired.rewards.local),
+        );
+        changed |= change(
+            list,
+            "RemoteReward",
+            ether(U256::from_limbs(current.rewards.remote.0)),
+            ether(U256::from_limbs(desired.rewards.remote.0)),
+        );
+        // Unless given, the AssetHub fee follows the pricing parameters as in `pricing-parameters`
+        if base_fee.is_none() {
+            base_fee = Some(assethub_base_fee(context, &desired).await?);
+        }
+        if changed && emit {
+            calls.push_bridge_hub(set_pricing_parameters(desired));
+        }
+    }
+
+    let modes = query_operating_modes(api, at).await?;
+    let operating_modes: [(_, _, _, OperatingModeCall); 3] = [
+        (
+            "InboundQueue",
+            &modes.inbound_queue,
+            &state.inbound_queue_operating_mode,
+            inbound_queue_operating_mode,
+        ),
+        (
+            "OutboundQueue",
+            &modes.outbound_queue,
+            &state.outbound_queue_operating_mode,
+            outbound_queue_operating_mode,
+        ),
+        (
+            "EthereumBeaconClient",
+            &modes.ethereum_client,
+            &state.ethereum_client_operating_mode,
+            ethereum_client_operating_mode,
+        ),
+    ];
+    for (name, current, desired, call) in operating_modes {
+        let Some(desired) = desired else {
+            continue;
+        };
+        let current = match current {
+            BasicOperatingMode::Normal => OperatingModeEnum::Normal,
+            BasicOperatingMode::Halted => OperatingModeEnum::Halted,
+        };
+        if change(
+            &mut changes.bridge_hub,
+            name,
+            format!("{current:?}"),
+            format!("{desired:?}"),
+        ) && emit
+        {
+            calls.push_bridge_hub(call(desired));
+        }
+    }
+
+    let gateway_address = query_gateway_address(api, at)
+        .await?
+        .map(|address| Address::from(address.0));
+    if let Some(args) = &state.gateway_address {
+        if change(
+            &mut changes.bridge_hub,
+            "EthereumGatewayAddress",
+            gateway_address.map_or("not set".into(), |address| address.to_checksum(None)),
+            args.gateway_address.to_checksum(None),
+        ) && emit
+        {
+            calls.push_bridge_hub(set_gateway_address(args));
+        }
+    }
+
+    // The Gateway operating mode and token transfer fees are only stored on Ethereum
+    if state.gateway_operating_mode.is_some() || state.token_transfer_fees.is_some() {
+        let ethereum_rpc = params
+            .ethereum_rpc
+            .as_ref()
+            .ok_or("--ethereum-rpc is required to read the Gateway state")?;
+        let gateway = gateway_address.ok_or("The gateway address is not set on BridgeHub")?;
+        let rpc = context.ethereum(ethereum_rpc).await?;
+
+        if let Some(args) = &state.gateway_operating_mode {
+            let current = match ethereum::gateway_operating_mode(&rpc, gateway).await? {
+                0 => "Normal".into(),
+                1 => "RejectingOutboundMessages".into(),
+                mode => format!("unknown ({mode})"),
+            };
+            if change(
+                &mut changes.gateway,
+                "OperatingMode",
+                current,
+                format!("{:?}", args.gateway_operating_mode),
+            ) && emit
+            {
+                calls.push_bridge_hub(gateway_operating_mode(&args.gateway_operating_mode));
+            }
+        }
+
+        if let Some(args) = &state.token_transfer_fees {
+            let (create_asset_xcm, transfer_asset_xcm, register_token) =
+                ethereum::gateway_token_transfer_fees(&rpc, gateway).await?;
+            let list = &mut changes.gateway;
+            let mut changed = change(
+                list,
+                "CreateAssetXcm",
+                native(create_asset_xcm),
+                native(args.create_asset_xcm.planck(NETWORK)?),
+            );
+            changed |= change(
+                list,
+                "TransferAssetXcm",
+                native(transfer_asset_xcm),
+                native(args.transfer_asset_xcm.planck(NETWORK)?),
+            );
+            changed |= change(
+                list,
+                "RegisterToken",
+                ether(register_token),
+                ether(args.register_token),
+            );
+            if changed && emit {
+                calls.push_bridge_hub(token_transfer_fees(args)?);
+            }
+        }
+    }
+
+    if let Some(desired) = base_fee {
+        let current =
+            query_assethub_base_fee(&context.asset_hub_api, context.asset_hub_block.hash).await?;
+        if change(
+            &mut changes.asset_hub,
+            "BridgeHubEthereumBaseFee",
+            current.map_or("not set".into(), native),
+            native(desired),
+        ) && emit
+        {
+            calls.push_asset_hub(set_assethub_fee(desired));
+        }
+    }
+
+    if let Some(args) = &state.assets {
+        for (index, asset) in commands::load_erc20_assets(args)?.iter().enumerate() {
+            plan_asset(context, asset, emit, &mut changes.asset_hub, &mut calls)
+                .await
+                .map_err(|e| format!("Invalid asset #{index} ({}): {e}", asset.contract))?;
+        }
+    }
+
+    eprintln!("Plan:");
+    let chains = [
+        ("BridgeHub", &changes.bridge_hub),
+        ("AssetHub", &changes.asset_hub),
+        ("Gateway", &changes.gateway),
+    ];
+    for (chain, list) in chains.iter().filter(|(_, list)| !list.is_empty()) {
+        eprintln!("  {chain}:");
+        for line in list.iter() {
+            eprintln!("    {line}");
+        }
+        provenance::record("Plan", chain, list);
+    }
+    if chains.iter().all(|(_, list)| list.is_empty()) {
+        eprintln!("  No changes");
+    }
+
+    Ok(calls)
+}
+
+// Compare an ERC20 token with its foreign asset on AssetHub, creating it if missing
+async fn plan_asset(
+    context: &Context,
+    asset: &Erc20Asset,
+    emit: bool,
+    changes: &mut Vec<String>,
+    calls: &mut Calls,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let contract: Address = asset
+        .contract
+        .parse()
+        .map_err(|_| "invalid contract address")?;
+    let location = get_asset_id(NETWORK.chain_id(), contract.into_array());
+    let current = query_foreign_asset(
+        &context.asset_hub_api,
+        context.asset_hub_block.hash,
+        &location,
+    )
+    .await?;
+
+    let asset_calls = asset_changes(contract, asset, current.as_ref(), changes)?;
+    if emit {
+        calls.extend(Calls::asset_hub(asset_calls));
+    }
+    Ok(())
+}
+
+// Add the differences of a foreign asset from an ERC20 token to the plan, returning the calls
+// applying them
+fn asset_changes(
+    contract: Address,
+    asset: &Erc20Asset,
+    current: Option<&(Asset, Metadata)>,
+    changes: &mut Vec<String>,
+) -> Result<Vec<AssetHubRuntimeCall>, String> {
+    let label = match &asset.symbol {
+        Some(symbol) => format!("{symbol} {contract}"),
+        None => contract.to_string(),
+    };
+    let Some((details, metadata)) = current else {
+        changes.push(format!("+ {label}: not registered"));
+        return commands::register_erc20_asset(asset);
+    };
+
+    let mut status_changed = change(
+        changes,
+        &format!("{label} MinBalance"),
+        details.min_balance.to_string(),
+        asset.min_balance.to_string(),
+    );
+    status_changed |= change(
+        changes,
+        &format!("{label} IsSufficient"),
+        details.is_sufficient.to_string(),
+        asset.is_sufficient.to_string(),
+    );
+
+    let current_name = String::from_utf8_lossy(&metadata.name.0).into_owned();
+    let current_symbol = String::from_utf8_lossy(&metadata.symbol.0).into_owned();
+    let (name, symbol, decimals, metadata_changed) =
+        match (&asset.name, &asset.symbol, asset.decimals) {
+            (Some(name), Some(symbol), Some(decimals)) => {
+                let mut changed = change(
+                    changes,
+                    &format!("{label} Name"),
+                    format!("{current_name:?}"),
+                    format!("{name:?}"),
+                );
+                changed |= change(
+                    changes,
+                    &format!("{label} Symbol"),
+                    format!("{current_symbol:?}"),
+                    format!("{symbol:?}"),
+                );
+                changed |= change(
+                    changes,
+                    &format!("{label} Decimals"),
+                    metadata.decimals.to_string(),
+                    decimals.to_string(),
+                );
+                (name.clone(), symbol.clone(), decimals, changed)
+            }
+            (None, None, None) => (current_name, current_symbol, metadata.decimals, false),
+            _ => return Err("name, symbol and decimals must be given together".into()),
+        };
+
+    // Fields which are not part of the desired state keep their current values
+    let update = |is_frozen| UpdateAssetArgs {
+        contract_id: contract,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        decimals,
+        min_balance: asset.min_balance,
+        is_sufficient: asset.is_sufficient,
+        is_frozen,
+    };
+    let mut calls = vec![];
+    if status_changed {
+        let is_frozen = matches!(details.status, AssetStatus::Frozen);
+        calls.push(commands::make_asset_sufficient(&update(is_frozen)));
+    }
+    if metadata_changed {
+        calls.push(commands::force_set_metadata(&update(metadata.is_frozen)));
+    }
+    Ok(calls)
+}
+
+// Add a change to the plan if the current and desired values differ
+fn change(changes: &mut Vec<String>, name: &str, current: String, desired: String) -> bool {
+    if current == desired {
+        return false;
+    }
+    changes.push(format!("~ {name}: {current} -> {desired}"));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::asset_hub_runtime::runtime_types::{
+        bounded_collections::bounded_vec::BoundedVec,
+        pallet_assets::types::{AssetDetails, AssetMetadata},
+    };
+    use super::*;
+    use codec::Encode;
+    use subxt::utils::AccountId32;
+
+    const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+    fn asset() -> Erc20Asset {
+        Erc20Asset {
+            contract: WETH.into(),
+            min_balance: 15_000_000_000_000,
+            is_sufficient: true,
+            name: Some("Wrapped Ether".into()),
+            symbol: Some("WETH".into()),
+            decimals: Some(18),
+        }
+    }
+
+    // The foreign asset of `asset()` on AssetHub
+    fn current() -> (Asset, Metadata) {
+        let owner = AccountId32(NETWORK.asset_owner());
+        let details = AssetDetails {
+            owner: owner.clone(),
+            issuer: owner.clone(),
+            admin: owner.clone(),
+            freezer: owner,
+            supply: 0,
+            deposit: 0,
+            min_balance: 15_000_000_000_000,
+            is_sufficient: true,
+            accounts: 0,
+            sufficients: 0,
+            approvals: 0,
+            status: AssetStatus::Live,
+        };
+        let metadata = AssetMetadata {
+            deposit: 0,
+            name: BoundedVec(b"Wrapped Ether".to_vec()),
+            symbol: BoundedVec(b"WETH".to_vec()),
+            decimals: 18,
+            is_frozen: false,
+        };
+        (details, metadata)
+    }
+
+    fn plan(
+        asset: &Erc20Asset,
+        current: Option<&(Asset, Metadata)>,
+    ) -> (Vec<String>, Vec<AssetHubRuntimeCall>) {
+        let mut changes = vec![];
+        let calls = asset_changes(WETH.parse().unwrap(), asset, current, &mut changes).unwrap();
+        (changes, calls)
+    }
+
+    fn update(asset: &Erc20Asset, is_frozen: bool) -> UpdateAssetArgs {
+        UpdateAssetArgs {
+            contract_id: WETH.parse().unwrap(),
+            name: asset.name.clone().unwrap(),
+            symbol: asset.symbol.clone().unwrap(),
+            decimals: asset.decimals.unwrap(),
+            min_balance: asset.min_balance,
+            is_sufficient: asset.is_sufficient,
+            is_frozen,
+        }
+    }
+
+    #[test]
+    fn compares_values() {
+        let mut changes = vec![];
+        assert!(!change(&mut changes, "Fee", "1".into(), "1".into()));
+        assert!(change(&mut changes, "Fee", "1".into(), "2".into()));
+        assert_eq!(changes, ["~ Fee: 1 -> 2"]);
+    }
+
+    #[test]
+    fn emits_nothing_without_changes() {
+        let (changes, calls) = plan(&asset(), Some(&current()));
+        assert!(changes.is_empty() && calls.is_empty());
+
+        // Metadata which is not part of the desired state is left as it is
+        let asset = Erc20Asset {
+            name: None,
+            symbol: None,
+            decimals: None,
+            ..asset()
+        };
+        let (changes, calls) = plan(&asset, Some(&current()));
+        assert!(changes.is_empty() && calls.is_empty());
+    }
+
+    #[test]
+    fn registers_missing_assets() {
+        let (changes, calls) = plan(&asset(), None);
+        assert_eq!(changes, [format!("+ WETH {WETH}: not registered")]);
+        assert_eq!(
+            calls.encode(),
+            commands::register_erc20_asset(&asset()).unwrap().encode()
+        );
+    }
+
+    #[test]
+    fn changes_only_the_fields_which_differ() {
+        let desired = Erc20Asset {
+            min_balance: 1,
+            ..asset()
+        };
+        let (mut details, metadata) = current();
+        details.status = AssetStatus::Frozen;
+        let (changes, calls) = plan(&desired, Some(&(details, metadata)));
+        assert_eq!(
+            changes,
+            [format!("~ WETH {WETH} MinBalance: 15000000000000 -> 1")]
+        );
+        // The asset stays frozen
+        assert_eq!(
+            calls.encode(),
+            vec![commands::make_asset_sufficient(&update(&desired, true))].encode()
+        );
+
+        let desired = Erc20Asset {
+            symbol: Some("wETH".into()),
+            ..asset()
+        };
+        let (changes, calls) = plan(&desired, Some(&current()));
+        assert_eq!(
+            changes,
+            [format!("~ wETH {WETH} Symbol: \"WETH\" -> \"wETH\"")]
+        );
+        assert_eq!(
+            calls.encode(),
+            vec![commands::force_set_metadata(&update(&desired, false))].encode()
+        );
+    }
+
+    #[test]
+    fn requires_metadata_fields_together() {
+        let asset = Erc20Asset {
+            decimals: None,
+            ..asset()
+        };
+        let mut changes = vec![];
+        assert_eq!(
+            asset_changes(
+                WETH.parse().unwrap(),
+                &asset,
+                Some(&current()),
+                &mut changes
+            )
+            .unwrap_err(),
+            "name, symbol and decimals must be given together"
+        );
+    }
+}
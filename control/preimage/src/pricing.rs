@@ -0,0 +1,276 @@
+use super::fees::print_outbound_fees;
+use super::helpers::{gwei, units};
+use super::queries::query_pricing_parameters;
+use super::snowbridge::build_pricing_parameters;
+use super::NETWORK;
+use crate::ethereum::EthereumRpc;
+use crate::{Context, NativeAmount, PricingParametersArgs, SuggestPricingArgs};
+use alloy_primitives::U256;
+
+// Most nodes return the fee history of at most 1024 blocks per request
+const FEE_HISTORY_MAX_BLOCKS: u64 = 1024;
+
+// Suggest pricing parameters from the price of ETH in the relay chain token and the gas prices
+// paid on Ethereum, and print the fees of the messages to Ethereum with them. The multiplier and
+// rewards are kept unless given.
+pub async fn suggest_pricing(
+    context: &Context,
+    params: &SuggestPricingArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for percentile in [params.reward_percentile, params.percentile] {
+        if !(0.0..=100.0).contains(&percentile) {
+            return Err(format!("Percentile {percentile} is not between 0 and 100").into());
+        }
+    }
+    let current =
+        query_pricing_parameters(&context.bridge_hub_api, context.bridge_hub_block.hash).await?;
+    let one = U256::from(10).pow(U256::from(18));
+
+    // The exchange rate is the price of the relay chain token in ETH
+    let (exchange_rate_numerator, exchange_rate_denominator) =
+        match (params.eth_price, params.eth_usd, params.native_usd) {
+            (Some(eth_price), _, _) => ratio(one, eth_price)?,
+            (None, Some(eth_usd), Some(native_usd)) => ratio(native_usd, eth_usd)?,
+            _ => return Err("--eth-price or the USD prices are required".into()),
+        };
+
+    let rpc = context.ethereum(&params.ethereum_rpc).await?;
+    let (oldest_block, newest_block, mut prices) =
+        gas_prices(&rpc, params.blocks, params.reward_percentile).await?;
+    if prices.is_empty() {
+        return Err("The Ethereum node returned no fee history".into());
+    }
+    prices.sort();
+    let fee_per_gas = percentile(&prices, params.percentile);
+
+    let (multiplier_numerator, multiplier_denominator) =
+        match (params.multiplier_numerator, params.multiplier_denominator) {
+            (Some(numerator), Some(denominator)) => (numerator, denominator),
+            _ => ratio(U256::from(current.multiplier.0.into_inner()), one)?,
+        };
+    let local_reward = params.local_reward.clone().unwrap_or_else(|| {
+        NativeAmount(units(U256::from(current.rewards.local), NETWORK.decimals()))
+    });
+    let remote_reward = params
+        .remote_reward
+        .unwrap_or(U256::from_limbs(current.rewards.remote.0));
+
+    let suggested = PricingParametersArgs {
+        exchange_rate_numerator,
+        exchange_rate_denominator,
+        multiplier_numerator,
+        multiplier_denominator,
+        fee_per_gas: U256::from(fee_per_gas),
+        local_reward,
+        remote_reward,
+    };
+    let pricing_params = build_pricing_parameters(&suggested)?;
+
+    println!("Ethereum fee history (blocks {oldest_block} to {newest_block}):");
+    println!(
+        "  Gas prices, with priority fees at p{}: min {}, median {}, p{} {}, max {}",
+        params.reward_percentile,
+        gwei(U256::from(prices[0])),
+        gwei(U256::from(percentile(&prices, 50.0))),
+        params.percentile,
+        gwei(U256::from(fee_per_gas)),
+        gwei(U256::from(prices[prices.len() - 1])),
+    );
+    println!("Suggested pricing parameters:");
+    println!("  pricing-parameters \\");
+    println!("    --exchange-rate-numerator {exchange_rate_numerator} \\");
+    println!("    --exchange-rate-denominator {exchange_rate_denominator} \\");
+    println!("    --multiplier-numerator {multiplier_numerator} \\");
+    println!("    --multiplier-denominator {multiplier_denominator} \\");
+    println!("    --fee-per-gas {} \\", units(suggested.fee_per_gas, 9));
+    println!("    --local-reward {} \\", suggested.local_reward.0);
+    println!("    --remote-reward {}", units(suggested.remote_reward, 18));
+
+    print_outbound_fees(context, Some(&pricing_params)).await
+}
+
+// Gas prices of the latest `blocks` blocks, with the oldest and newest block numbers. The fee
+// history is requested in chunks, from the latest block back to the genesis block at most.
+async fn gas_prices(
+    rpc: &EthereumRpc,
+    blocks: u64,
+    reward_percentile: f64,
+) -> Result<(u64, u64, Vec<u128>), Box<dyn std::error::Error>> {
+    let mut prices = vec![];
+    let (mut oldest_block, mut newest_block) = (0, None);
+    let mut newest = None;
+    while (prices.len() as u64) < blocks {
+        let count = (blocks - prices.len() as u64).min(FEE_HISTORY_MAX_BLOCKS);
+        let (oldest, chunk) = rpc.fee_history(count, newest, reward_percentile).await?;
+        if chunk.is_empty() {
+            break;
+        }
+        newest_block.get_or_insert(oldest + chunk.len() as u64 - 1);
+        oldest_block = oldest;
+        prices.extend(chunk);
+        if oldest == 0 {
+            break;
+        }
+        newest = Some(oldest - 1);
+    }
+    Ok((oldest_block, newest_block.unwrap_or_default(), prices))
+}
+
+// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[u128], percentile: f64) -> u128 {
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+// Reduce a ratio to the 64-bit numerator and denominator taken by `pricing-parameters`
+fn ratio(numerator: U256, denominator: U256) -> Result<(u64, u64), String> {
+    let (mut a, mut b) = (numerator, denominator);
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+    let reduced = (numerator / a)
+        .try_into()
+        .ok()
+        .zip((denominator / a).try_into().ok());
+    reduced.ok_or(format!(
+        "{numerator}/{denominator} cannot be reduced to 64-bit integers, try prices with fewer digits"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{self, Response};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    // Serve the fee history of a chain at block `latest`, where the base fee of each block is
+    // its number and the priority fee is 1 wei, recording the requested block counts and newest
+    // blocks. Like most nodes, the history is limited to 1024 blocks per request.
+    async fn ethereum_node(latest: u64) -> (String, Arc<Mutex<Vec<(u64, String)>>>) {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        let url = stub::serve(move |request| {
+            let request = request.json();
+            let params = &request["params"];
+            let quantity = |value: &Value| {
+                u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+            };
+            let count = quantity(&params[0]);
+            let newest = match params[1].as_str().unwrap() {
+                "latest" => latest,
+                _ => quantity(&params[1]),
+            };
+            recorded
+                .lock()
+                .unwrap()
+                .push((count, params[1].as_str().unwrap().to_owned()));
+
+            let oldest = (newest + 1).saturating_sub(count.min(FEE_HISTORY_MAX_BLOCKS));
+            let base_fees: Vec<_> = (oldest..=newest + 1)
+                .map(|block| format!("{block:#x}"))
+                .collect();
+            let rewards: Vec<_> = (oldest..=newest).map(|_| json!(["0x1"])).collect();
+            Response::json(&json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {
+                    "oldestBlock": format!("{oldest:#x}"),
+                    "baseFeePerGas": base_fees,
+                    "reward": rewards,
+                },
+            }))
+        })
+        .await;
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn requests_fee_history_in_chunks() {
+        let (url, requests) = ethereum_node(2999).await;
+
+        let (oldest, newest, prices) = gas_prices(&EthereumRpc::new(&url), 2500, 50.0)
+            .await
+            .unwrap();
+
+        assert_eq!((oldest, newest), (500, 2999));
+        assert_eq!(prices.len(), 2500);
+        // Base fees plus the priority fee, from the newest chunk to the oldest one
+        assert_eq!(prices[0], 1977);
+        assert_eq!(prices[2048], 501);
+        assert_eq!(prices[2499], 952);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                (1024, "latest".to_owned()),
+                (1024, "0x7b7".to_owned()),
+                (452, "0x3b7".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn ends_fee_history_at_the_pinned_block() {
+        let (url, requests) = ethereum_node(2999).await;
+
+        let rpc = EthereumRpc::new(&url).at(2000);
+        let (oldest, newest, prices) = gas_prices(&rpc, 1500, 50.0).await.unwrap();
+
+        assert_eq!((oldest, newest), (501, 2000));
+        assert_eq!(prices.len(), 1500);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [(1024, "0x7d0".to_owned()), (476, "0x3d0".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_fee_history_at_genesis() {
+        let (url, requests) = ethereum_node(1500).await;
+
+        let (oldest, newest, prices) = gas_prices(&EthereumRpc::new(&url), 2500, 50.0)
+            .await
+            .unwrap();
+
+        assert_eq!((oldest, newest), (0, 1500));
+        assert_eq!(prices.len(), 1501);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn takes_nearest_rank_percentiles() {
+        let sorted = [10, 20, 30, 40, 50];
+        assert_eq!(percentile(&sorted, 0.0), 10);
+        assert_eq!(percentile(&sorted, 1.0), 10);
+        assert_eq!(percentile(&sorted, 20.0), 10);
+        assert_eq!(percentile(&sorted, 21.0), 20);
+        assert_eq!(percentile(&sorted, 50.0), 30);
+        assert_eq!(percentile(&sorted, 100.0), 50);
+        assert_eq!(percentile(&[7], 0.0), 7);
+        assert_eq!(percentile(&[7], 100.0), 7);
+    }
+
+    #[test]
+    fn reduces_ratios() {
+        let one = U256::from(10).pow(U256::from(18));
+        // An ETH price of 400 DOT
+        assert_eq!(ratio(one, U256::from(400) * one), Ok((1, 400)));
+        assert_eq!(ratio(U256::from(6), U256::from(4)), Ok((3, 2)));
+        assert_eq!(ratio(U256::ZERO, U256::from(5)), Ok((0, 1)));
+        assert_eq!(
+            ratio(U256::from(u64::MAX), U256::from(1)),
+            Ok((u64::MAX, 1))
+        );
+    }
+
+    #[test]
+    fn rejects_ratios_overflowing_64_bits() {
+        // Coprime, with a numerator of 65 bits
+        let numerator = U256::from(u64::MAX) + U256::from(2);
+        let error = ratio(numerator, U256::from(3)).unwrap_err();
+        assert!(
+            error.contains("cannot be reduced to 64-bit integers"),
+            "{error}"
+        );
+    }
+}
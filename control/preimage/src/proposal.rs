@@ -0,0 +1,262 @@
+use super::helpers::{fellowship_xcm, read_preimage_calls, scheduled, send_calls, Calls};
+use super::relay_runtime::runtime_types::{pallet_preimage, pallet_whitelist};
+use super::{asset_hub_runtime, bridge_hub_runtime, commands, relay_runtime, NETWORK};
+use crate::opengov::Submission;
+use crate::provenance;
+use crate::{dynamic, manifest, ChainEnum, Command, Context, ManifestStep, ParachainEnum};
+use clap::Parser;
+use codec::{Decode, Encode};
+use snowbridge_preimage_chopsticks::Schedule;
+use sp_crypto_hashing::blake2_256;
+
+// Build the call for a command, dispatched with Root origin once the proposal is enacted
+pub async fn call(
+    context: &Context,
+    command: &Command,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let calls = build_calls(context, command).await?;
+    Ok(send_calls(context, calls).await?.encode())
+}
+
+// Build the preimage for a call, scheduling it and wrapping it in a sudo call if requested
+pub fn preimage(
+    call: &[u8],
+    schedule: Option<Schedule>,
+    sudo: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let call = relay_runtime::RuntimeCall::decode(&mut &call[..])?;
+
+    let call = match schedule {
+        Some(schedule) => scheduled(call, schedule),
+        None => call,
+    };
+
+    let final_call = if sudo {
+        super::sudo(Box::new(call)).ok_or(format!("Sudo is not available on {NETWORK}"))?
+    } else {
+        call
+    };
+
+    Ok(final_call.encode())
+}
+
+// Build the calls submitting a preimage to the Whitelisted Caller track: the public referendum
+// on the relay chain dispatches the preimage once the Fellowship has whitelisted its hash
+pub async fn whitelisted_caller(
+    context: &Context,
+    preimage: &[u8],
+    enactment_after: u32,
+) -> Result<Vec<Submission>, Box<dyn std::error::Error>> {
+    let (dispatch, submit) = whitelisted_referendum(preimage, enactment_after)?;
+    let note_preimage =
+        relay_runtime::RuntimeCall::Preimage(pallet_preimage::pallet::Call::note_preimage {
+            bytes: dispatch.clone(),
+        });
+
+    let whitelist_call =
+        relay_runtime::RuntimeCall::Whitelist(pallet_whitelist::pallet::Call::whitelist_call {
+            call_hash: blake2_256(preimage).into(),
+        });
+    let whitelist = if NETWORK.fellowship_on_relay() {
+        Submission::new(
+            "Whitelist.whitelist_call",
+            format!("Proposed by the Fellowship with FellowshipReferenda.submit on {NETWORK}"),
+            whitelist_call.encode(),
+        )
+    } else {
+        Submission::new(
+            "Whitelist.whitelist_call XCM",
+            format!(
+                "Sent by the Fellowship to {NETWORK} with PolkadotXcm.send on the Collectives chain"
+            ),
+            fellowship_xcm(context, whitelist_call).await?.encode(),
+        )
+    };
+
+    Ok(vec![
+        Submission::new(
+            "Preimage.note_preimage",
+            format!("Submitted on {NETWORK} to note the preimage of the referendum"),
+            note_preimage.encode(),
+        ),
+        Submission::new(
+            "Whitelist.dispatch_whitelisted_call_with_preimage",
+            "Preimage of the referendum, dispatching the proposal once whitelisted".into(),
+            dispatch,
+        ),
+        Submission::new(
+            "Referenda.submit",
+            format!("Submitted on {NETWORK} to open the referendum with WhitelistedCaller origin"),
+            submit,
+        ),
+        whitelist,
+    ])
+}
+
+// Build the preimage of the referendum, dispatching the proposal once its hash is whitelisted,
+// and the `Referenda.submit` call opening the referendum on the Whitelisted Caller track
+fn whitelisted_referendum(
+    preimage: &[u8],
+    enactment_after: u32,
+) -> Result<(Vec<u8>, Vec<u8>), Box<dyn std::error::Error>> {
+    let call = relay_runtime::RuntimeCall::decode(&mut &preimage[..])?;
+
+    let dispatch = relay_runtime::RuntimeCall::Whitelist(
+        pallet_whitelist::pallet::Call::dispatch_whitelisted_call_with_preimage {
+            call: Box::new(call),
+        },
+    )
+    .encode();
+
+    // Referenda is encoded from the metadata, as Kusama also has an instance of the pallet for
+    // the Fellowship, and the generated types of the two instances are told apart by number
+    let submit = dynamic::encode_call(
+        relay_runtime::METADATA,
+        "Referenda",
+        "submit",
+        &serde_json::json!({
+            "proposal_origin": {"Origins": "WhitelistedCaller"},
+            "proposal": {"Lookup": {
+                "hash": format!("0x{}", hex::encode(blake2_256(&dispatch))),
+                "len": dispatch.len(),
+            }},
+            "enactment_moment": {"After": enactment_after},
+        }),
+    )?;
+
+    Ok((dispatch, submit))
+}
+
+// Build the calls for a command, grouped by the chain they are dispatched on
+async fn build_calls(
+    context: &Context,
+    command: &Command,
+) -> Result<Calls, Box<dyn std::error::Error>> {
+    let calls = match command {
+        Command::UpdateAsset(params) => Calls::asset_hub(vec![
+            commands::make_asset_sufficient(params),
+            commands::force_set_metadata(params),
+        ]),
+        Command::RegisterEther(params) => {
+            let (register_ether_call, set_ether_metadata_call) =
+                commands::register_ether(context, params).await?;
+            Calls::asset_hub(vec![register_ether_call, set_ether_metadata_call])
+        }
+        Command::RegisterErc20(params) => {
+            Calls::asset_hub(commands::register_erc20_assets(context, params).await?)
+        }
+        Command::UpgradeRuntime(params) => {
+            let code_hash = commands::runtime_code_hash(&params.wasm)?;
+            let mut calls = match params.chain {
+                ParachainEnum::BridgeHub => {
+                    Calls::bridge_hub(vec![commands::bridge_hub_authorize_upgrade(code_hash)])
+                }
+                ParachainEnum::AssetHub => {
+                    Calls::asset_hub(vec![commands::asset_hub_authorize_upgrade(code_hash)])
+                }
+            };
+            for path in params.then.iter() {
+                calls.extend(Calls::relay(read_preimage_calls(path)?));
+            }
+            calls
+        }
+        Command::Call(params) => {
+            let (metadata, name) = match params.chain {
+                ChainEnum::Relay => (relay_runtime::METADATA, "Relay"),
+                ChainEnum::BridgeHub => (bridge_hub_runtime::METADATA, "BridgeHub"),
+                ChainEnum::AssetHub => (asset_hub_runtime::METADATA, "AssetHub"),
+            };
+            let encoded =
+                dynamic::encode_call(metadata, &params.pallet, &params.call, &params.args)?;
+            eprintln!("{}:", name);
+            eprintln!("  Call: {}.{}", params.pallet, params.call);
+            eprintln!("  Encoded: 0x{}", hex::encode(&encoded));
+            provenance::record(name, "Encoded", format!("0x{}", hex::encode(&encoded)));
+            match params.chain {
+                ChainEnum::Relay => {
+                    Calls::relay(vec![relay_runtime::RuntimeCall::decode(&mut &encoded[..])?])
+                }
+                ChainEnum::BridgeHub => {
+                    Calls::bridge_hub(vec![bridge_hub_runtime::RuntimeCall::decode(
+                        &mut &encoded[..],
+                    )?])
+                }
+                ChainEnum::AssetHub => {
+                    Calls::asset_hub(vec![asset_hub_runtime::RuntimeCall::decode(
+                        &mut &encoded[..],
+                    )?])
+                }
+            }
+        }
+        Command::Build(params) => {
+            let mut calls = Calls::default();
+            for (index, step) in manifest::load_steps(&params.manifest, &params.vars)?
+                .into_iter()
+                .enumerate()
+            {
+                let ManifestStep { command } = ManifestStep::try_parse_from(&step.args)
+                    .map_err(|e| format!("Invalid step {}: {e}", index + 1))?;
+                if let Command::Build(_) = command {
+                    return Err(
+                        format!("Invalid step {}: manifests cannot be nested", index + 1).into(),
+                    );
+                }
+                match &step.description {
+                    Some(description) => {
+                        eprintln!("Step {}: {} ({})", index + 1, step.args[0], description)
+                    }
+                    None => eprintln!("Step {}: {}", index + 1, step.args[0]),
+                }
+                calls.extend(Box::pin(build_calls(context, &command)).await?);
+            }
+            calls
+        }
+        Command::Assets(_) => return Err("assets does not build a preimage".into()),
+        command => super::snowbridge::build_calls(context, command).await?,
+    };
+
+    Ok(calls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The Referenda call is encoded from the metadata, so it is checked against the bundled
+    // metadata and the generated types of the relay chain
+    #[test]
+    fn decodes_the_whitelisted_referendum() {
+        let preimage = relay_runtime::RuntimeCall::System(
+            relay_runtime::runtime_types::frame_system::pallet::Call::remark {
+                remark: vec![1, 2, 3],
+            },
+        )
+        .encode();
+        let (dispatch, submit) = whitelisted_referendum(&preimage, 100).unwrap();
+        let decoder = crate::decoder(NETWORK).unwrap();
+
+        assert_eq!(
+            decoder.decode(&dispatch).unwrap().tree(),
+            "Whitelist.dispatch_whitelisted_call_with_preimage
+  call: System.remark
+    remark: 0x010203
+"
+        );
+        assert_eq!(
+            decoder.decode(&submit).unwrap().tree(),
+            format!(
+                "Referenda.submit
+  proposal_origin: Origins(WhitelistedCaller)
+  proposal: Lookup
+    hash: 0x{}
+    len: {}
+  enactment_moment: After(100)
+",
+                hex::encode(blake2_256(&dispatch)),
+                dispatch.len()
+            )
+        );
+        let call = relay_runtime::RuntimeCall::decode(&mut &submit[..]).unwrap();
+        assert_eq!(call.encode(), submit);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::decode::Decoder;
+use crate::network::Network;
+use crate::opengov::{self, Submission};
+use crate::{ChainEnum, Context, PinnedBlock};
+use serde::Serialize;
+use sp_crypto_hashing::blake2_256;
+use std::sync::Mutex;
+use subxt::dynamic::Value;
+use subxt::ext::scale_value::At;
+use subxt::utils::H256;
+
+/// Git revision the tool was built from, set by the build script
+pub const REVISION: &str = env!("GIT_REVISION");
+
+/// A value derived while building the preimage, such as a fee computed from the chain state or
+/// a storage key, under the heading it is printed with
+#[derive(Debug, Serialize)]
+pub struct Derived {
+    pub section: String,
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
+/// Weight of a call queried with `TransactionPaymentCallApi.query_call_info`
+#[derive(Copy, Clone, Debug, Serialize)]
+pub struct QueriedWeight {
+    pub ref_time: u64,
+    pub proof_size: u64,
+    pub block_number: u32,
+    pub block_hash: H256,
+}
+
+/// Weight required by a `Transact` instruction, increased from the queried weight as a buffer
+#[derive(Debug, Serialize)]
+pub struct Weight {
+    pub chain: ChainEnum,
+    pub call_hash: H256,
+    pub queried: QueriedWeight,
+    pub ref_time: u64,
+    pub proof_size: u64,
+}
+
+/// Runtime of a chain at the block the preimage was built against
+#[derive(Debug, Serialize)]
+pub struct Runtime {
+    pub block: PinnedBlock,
+    pub spec_version: u32,
+    pub transaction_version: u32,
+    pub metadata_hash: H256,
+    /// Hash of the metadata bundled in the tool and used to encode the calls, which differs
+    /// from `metadata_hash` if the runtime was upgraded since the bindings were updated
+    pub bundled_metadata_hash: H256,
+}
+
+// Values recorded by the commands of each network while the preimage is built
+static DERIVED: Mutex<Vec<Derived>> = Mutex::new(Vec::new());
+static WEIGHTS: Mutex<Vec<Weight>> = Mutex::new(Vec::new());
+
+/// Record a derived value for the manifest. Amounts are recorded as strings, as they may not
+/// fit in the integers of JSON parsers.
+pub fn record(section: &str, name: &str, value: impl Serialize) {
+    let value = serde_json::to_value(value).expect("derived values are serializable");
+    DERIVED.lock().expect("not poisoned").push(Derived {
+        section: section.into(),
+        name: name.into(),
+        value,
+    });
+}
+
+pub fn record_weight(weight: Weight) {
+    WEIGHTS.lock().expect("not poisoned").push(weight);
+}
+
+/// Build the manifest of a preimage: the preimage with its decoded calls, the values derived
+/// and weights queried while building it, the runtimes and tool revision it was built with, and
+/// the Ethereum and beacon blocks it was built at
+pub async fn manifest(
+    context: &Context,
+    decoder: &Decoder,
+    network: Network,
+    preimage: &[u8],
+    submission: Option<&[Submission]>,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut manifest = serde_json::json!({
+        "network": network.to_string(),
+        "preimage": format!("0x{}", hex::encode(preimage)),
+        "hash": H256(blake2_256(preimage)),
+        "length": preimage.len(),
+        "call": decoder.decode(preimage)?.json(),
+        "derived": std::mem::take(&mut *DERIVED.lock().expect("not poisoned")),
+        "weights": std::mem::take(&mut *WEIGHTS.lock().expect("not poisoned")),
+        "runtimes": {
+            "relay": runtime(context, decoder, ChainEnum::Relay).await?,
+            "bridge_hub": runtime(context, decoder, ChainEnum::BridgeHub).await?,
+            "asset_hub": runtime(context, decoder, ChainEnum::AssetHub).await?,
+        },
+        "tool": {
+            "version": env!("CARGO_PKG_VERSION"),
+            "revision": REVISION,
+            "arguments": std::env::args().skip(1).collect::<Vec<_>>(),
+        },
+    });
+    // Recorded only if they were queried, as commands which don't read Ethereum need no node
+    if let Some(block) = *context.ethereum_block.lock().expect("not poisoned") {
+        manifest["ethereum"] = serde_json::json!({ "block": block });
+    }
+    if let Some(block_root) = *context.beacon_block_root.lock().expect("not poisoned") {
+        manifest["beacon"] = serde_json::json!({ "block_root": block_root });
+    }
+    if let Some(submission) = submission {
+        manifest["submission"] = opengov::json(submission);
+    }
+    Ok(manifest)
+}
+
+async fn runtime(
+    context: &Context,
+    decoder: &Decoder,
+    chain: ChainEnum,
+) -> Result<Runtime, Box<dyn std::error::Error>> {
+    let (api, block) = (context.api(chain), context.block(chain));
+    let version = api
+        .runtime_api()
+        .at(block.hash)
+        .call(subxt::dynamic::runtime_api_call(
+            "Core",
+            "version",
+            Vec::<Value>::new(),
+        ))
+        .await?
+        .to_value()?;
+    let field = |name: &str| {
+        version
+            .at(name)
+            .and_then(|value| value.as_u128())
+            .and_then(|value| u32::try_from(value).ok())
+            .ok_or(format!("Missing {name} in the runtime version of {chain}"))
+    };
+    Ok(Runtime {
+        block,
+        spec_version: field("spec_version")?,
+        transaction_version: field("transaction_version")?,
+        metadata_hash: H256(api.metadata().hasher().hash()),
+        bundled_metadata_hash: H256(decoder.metadata(chain).hasher().hash()),
+    })
+}
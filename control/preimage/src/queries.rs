@@ -0,0 +1,179 @@
+use super::bridge_hub_runtime;
+use super::bridge_hub_runtime::ethereum_system::storage::types::pricing_parameters::PricingParameters;
+use codec::Decode;
+use subxt::{utils::H160, utils::H256, OnlineClient, PolkadotConfig};
+
+use super::bridge_hub_runtime::runtime_types::snowbridge_core::operating_mode::BasicOperatingMode;
+use super::bridge_hub_runtime::runtime_types::snowbridge_core::outbound::v1::AgentExecuteCommand;
+use super::bridge_hub_runtime::runtime_types::snowbridge_core::outbound::v1::Command;
+use super::bridge_hub_runtime::runtime_types::snowbridge_core::outbound::Fee;
+
+pub async fn calculate_delivery_fee(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+    params: &PricingParameters,
+) -> Result<Fee<u128>, Box<dyn std::error::Error>> {
+    let command = Command::AgentExecute {
+        agent_id: H256::zero(),
+        command: AgentExecuteCommand::TransferToken {
+            token: H160::zero(),
+            recipient: H160::zero(),
+            amount: 0,
+        },
+    };
+    calculate_fee(api, at, command, Some(params)).await
+}
+
+// Fee for sending a command to Ethereum, with the given pricing parameters or else the current
+// ones
+pub async fn calculate_fee(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+    command: Command,
+    params: Option<&PricingParameters>,
+) -> Result<Fee<u128>, Box<dyn std::error::Error>> {
+    let runtime_api_call = bridge_hub_runtime::apis()
+        .outbound_queue_api()
+        .calculate_fee(command, params.cloned());
+    let fee = api.runtime_api().at(at).call(runtime_api_call).await?;
+
+    Ok(fee)
+}
+
+pub async fn query_pricing_parameters(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+) -> Result<PricingParameters, Box<dyn std::error::Error>> {
+    let storage_query = bridge_hub_runtime::storage()
+        .ethereum_system()
+        .pricing_parameters();
+    let params = api
+        .storage()
+        .at(at)
+        .fetch_or_default(&storage_query)
+        .await?;
+
+    Ok(params)
+}
+
+// Resolve the sibling parachain which owns an agent from the registered channels
+pub async fn query_agent_para_id(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+    agent_id: H256,
+) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+    let storage_query = bridge_hub_runtime::storage()
+        .ethereum_system()
+        .channels_iter();
+    let mut channels = api.storage().at(at).iter(storage_query).await?;
+    while let Some(kv) = channels.next().await {
+        let kv = kv?;
+        if kv.value.agent_id == agent_id {
+            return Ok(Some(kv.value.para_id.0));
+        }
+    }
+
+    Ok(None)
+}
+
+// Operating modes of the bridge pallets on BridgeHub
+pub struct OperatingModes {
+    pub inbound_queue: BasicOperatingMode,
+    pub outbound_queue: BasicOperatingMode,
+    pub ethereum_client: BasicOperatingMode,
+}
+
+pub async fn query_operating_modes(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+) -> Result<OperatingModes, Box<dyn std::error::Error>> {
+    let storage = api.storage().at(at);
+    let inbound_queue = storage
+        .fetch_or_default(
+            &bridge_hub_runtime::storage()
+                .ethereum_inbound_queue()
+                .operating_mode(),
+        )
+        .await?;
+    let outbound_queue = storage
+        .fetch_or_default(
+            &bridge_hub_runtime::storage()
+                .ethereum_outbound_queue()
+                .operating_mode(),
+        )
+        .await?;
+    let ethereum_client = storage
+        .fetch_or_default(
+            &bridge_hub_runtime::storage()
+                .ethereum_beacon_client()
+                .operating_mode(),
+        )
+        .await?;
+
+    Ok(OperatingModes {
+        inbound_queue,
+        outbound_queue,
+        ethereum_client,
+    })
+}
+
+// Block root and slot of the latest finalized beacon header known to the light client, if
+// initialized
+pub async fn query_finalized_beacon_checkpoint(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+) -> Result<Option<(H256, u64)>, Box<dyn std::error::Error>> {
+    let storage = api.storage().at(at);
+    let block_root = storage
+        .fetch_or_default(
+            &bridge_hub_runtime::storage()
+                .ethereum_beacon_client()
+                .latest_finalized_block_root(),
+        )
+        .await?;
+    let state = storage
+        .fetch(
+            &bridge_hub_runtime::storage()
+                .ethereum_beacon_client()
+                .finalized_beacon_state(block_root),
+        )
+        .await?;
+
+    Ok(state.map(|state| (block_root, state.slot)))
+}
+
+// Slot of the latest finalized beacon header known to the light client, if initialized
+pub async fn query_finalized_beacon_slot(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    Ok(query_finalized_beacon_checkpoint(api, at)
+        .await?
+        .map(|(_, slot)| slot))
+}
+
+// Gateway contract address configured on BridgeHub, if set
+pub async fn query_gateway_address(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+) -> Result<Option<H160>, Box<dyn std::error::Error>> {
+    let storage_key = sp_crypto_hashing::twox_128(b":EthereumGatewayAddress:").to_vec();
+    let value = api.storage().at(at).fetch_raw(storage_key).await?;
+
+    Ok(value
+        .map(|value| H160::decode(&mut value.as_slice()))
+        .transpose()?)
+}
+
+// Fee for delivering a message to Ethereum charged by AssetHub, if set
+pub async fn query_assethub_base_fee(
+    api: &OnlineClient<PolkadotConfig>,
+    at: H256,
+) -> Result<Option<u128>, Box<dyn std::error::Error>> {
+    let storage_key = sp_crypto_hashing::twox_128(b":BridgeHubEthereumBaseFee:").to_vec();
+    let value = api.storage().at(at).fetch_raw(storage_key).await?;
+
+    Ok(value
+        .map(|value| u128::decode(&mut value.as_slice()))
+        .transpose()?)
+}
@@ -0,0 +1,133 @@
+use crate::{provenance, Context, ScheduleArgs};
+use snowbridge_preimage_chopsticks::Schedule;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Resolve the schedule options to the relay chain block the proposal is scheduled at. A time
+/// is converted to the block expected at that time, from the timestamp of the block the relay
+/// chain is queried at and its expected block time.
+pub async fn resolve(
+    args: &ScheduleArgs,
+    context: &Context,
+) -> Result<Option<Schedule>, Box<dyn std::error::Error>> {
+    if let Some(after) = args.schedule_after {
+        provenance::record("Schedule", "After", after);
+        return Ok(Some(Schedule::After(after)));
+    }
+    let target = match (args.schedule_at, args.schedule_at_time) {
+        (Some(target), _) => target,
+        (None, Some(time)) => block_at_time(context, time).await?,
+        (None, None) => return Ok(None),
+    };
+
+    // The Scheduler rejects blocks which are not in the future
+    let current = context.relay_block.number;
+    if target <= current {
+        return Err(format!(
+            "Block {target} is in the past, the relay chain is at block {current}"
+        )
+        .into());
+    }
+    provenance::record("Schedule", "At", target);
+    Ok(Some(Schedule::At(target)))
+}
+
+async fn block_at_time(
+    context: &Context,
+    time: SystemTime,
+) -> Result<u32, Box<dyn std::error::Error>> {
+    let (api, block) = (&context.relay_api, context.relay_block);
+    let now: u64 = api
+        .storage()
+        .at(block.hash)
+        .fetch(&subxt::dynamic::storage("Timestamp", "Now", ()))
+        .await?
+        .ok_or("Timestamp not found on the relay chain")?
+        .as_type()?;
+    let block_time: u64 = api
+        .constants()
+        .at(&subxt::dynamic::constant("Babe", "ExpectedBlockTime"))?
+        .as_type()?;
+
+    let target = expected_block(block.number, now, block_time, time)?;
+    eprintln!(
+        "Block {target} is expected at {}, at {block_time} ms per block",
+        humantime::format_rfc3339_seconds(time)
+    );
+    provenance::record(
+        "Schedule",
+        "Time",
+        serde_json::json!({
+            "time": humantime::format_rfc3339_seconds(time).to_string(),
+            "block_number": block.number,
+            "timestamp": now,
+            "block_time": block_time,
+        }),
+    );
+    Ok(target)
+}
+
+// Block expected at `time`, counting whole blocks of `block_time` ms from the block `number`
+// with the timestamp `now`
+fn expected_block(number: u32, now: u64, block_time: u64, time: SystemTime) -> Result<u32, String> {
+    let time_str = humantime::format_rfc3339_seconds(time);
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| u64::try_from(duration.as_millis()).ok())
+        .ok_or_else(|| format!("{time_str} is out of range"))?;
+    let remaining = millis
+        .checked_sub(now)
+        .ok_or_else(|| format!("{time_str} is in the past"))?;
+    if block_time == 0 {
+        return Err("The expected block time is zero".into());
+    }
+    u32::try_from(remaining.div_ceil(block_time))
+        .ok()
+        .and_then(|blocks| number.checked_add(blocks))
+        .ok_or_else(|| format!("{time_str} is too far ahead to be scheduled"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const NOW: u64 = 1_735_689_600_000;
+
+    fn at(millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn counts_blocks_until_the_time() {
+        assert_eq!(expected_block(100, NOW, 6000, at(NOW + 60_000)), Ok(110));
+        // A partial block rounds up, so the proposal is not enacted early
+        assert_eq!(expected_block(100, NOW, 6000, at(NOW + 60_001)), Ok(111));
+        assert_eq!(expected_block(100, NOW, 6000, at(NOW + 1)), Ok(101));
+        assert_eq!(expected_block(100, NOW, 6000, at(NOW)), Ok(100));
+    }
+
+    #[test]
+    fn rejects_times_which_cannot_be_scheduled() {
+        assert_eq!(
+            expected_block(100, NOW, 6000, at(NOW - 1000)),
+            Err("2024-12-31T23:59:59Z is in the past".into())
+        );
+        assert_eq!(
+            expected_block(100, NOW, 0, at(NOW + 1000)),
+            Err("The expected block time is zero".into())
+        );
+        // The block number would overflow
+        let time = at(NOW + 6000 * 10);
+        assert_eq!(
+            expected_block(u32::MAX - 5, NOW, 6000, time),
+            Err("2025-01-01T00:01:00Z is too far ahead to be scheduled".into())
+        );
+        assert_eq!(expected_block(u32::MAX - 10, NOW, 6000, time), Ok(u32::MAX));
+        // The number of blocks does not fit in a block number
+        let time = at(NOW + (u32::MAX as u64 + 1) * 6000);
+        assert!(expected_block(0, NOW, 6000, time)
+            .unwrap_err()
+            .ends_with(" is too far ahead to be scheduled"));
+    }
+}
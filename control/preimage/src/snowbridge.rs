@@ -0,0 +1,741 @@
+use super::commands;
+use super::helpers::{ether, exchange_rate, fixed, gwei, native, Calls};
+use super::queries::{
+    calculate_delivery_fee, query_agent_para_id, query_assethub_base_fee,
+    query_finalized_beacon_checkpoint, query_finalized_beacon_slot, query_gateway_address,
+    query_operating_modes, query_pricing_parameters,
+};
+use super::NETWORK;
+use crate::beacon;
+use crate::ethereum;
+use crate::provenance;
+use crate::{
+    AgentTransferArgs, Command, Context, ForceCheckpointArgs, GatewayAddressArgs,
+    GatewayOperatingModeEnum, OperatingModeEnum, PricingParametersArgs, StatusArgs,
+    TokenTransferFeesArgs, UpgradeArgs,
+};
+use alloy_primitives::{keccak256, Address, U256};
+use codec::{Decode, Encode};
+use sp_arithmetic::FixedU128;
+use sp_crypto_hashing::twox_128;
+use subxt::utils::Static;
+
+type CheckpointUpdate = snowbridge_beacon_primitives::CheckpointUpdate<512>;
+
+use super::asset_hub_runtime::RuntimeCall as AssetHubRuntimeCall;
+
+use super::bridge_hub_runtime::runtime_types::{
+    snowbridge_core::{
+        operating_mode::BasicOperatingMode,
+        outbound::v1::{Initializer, OperatingMode},
+        pricing::{PricingParameters, Rewards},
+    },
+    snowbridge_pallet_ethereum_client, snowbridge_pallet_inbound_queue,
+    snowbridge_pallet_outbound_queue, snowbridge_pallet_system,
+};
+use super::bridge_hub_runtime::RuntimeCall as BridgeHubRuntimeCall;
+
+// Build the calls for a command of the Snowbridge pallets, grouped by the chain they are
+// dispatched on
+pub async fn build_calls(
+    context: &Context,
+    command: &Command,
+) -> Result<Calls, Box<dyn std::error::Error>> {
+    let calls = match command {
+        Command::ForceCheckpoint(params) => {
+            let call = force_checkpoint(context, params).await?;
+            Calls::bridge_hub(vec![call])
+        }
+        Command::VerifyCheckpoint(_) => {
+            return Err("verify-checkpoint does not build a preimage".into());
+        }
+        Command::Status(_) => {
+            return Err("status does not build a preimage".into());
+        }
+        Command::QuoteFees(_) => {
+            return Err("quote-fees does not build a preimage".into());
+        }
+        Command::SuggestPricing(_) => {
+            return Err("suggest-pricing does not build a preimage".into());
+        }
+        Command::Plan(params) => {
+            if !params.emit {
+                return Err("plan builds a preimage only with --emit".into());
+            }
+            let calls = super::plan::plan(context, params).await?;
+            if calls.is_empty() {
+                return Err("Nothing to change, the chains match the desired state".into());
+            }
+            calls
+        }
+        Command::Initialize(params) => {
+            let (set_pricing_parameters, set_ethereum_fee) =
+                pricing_parameters(context, &params.pricing_parameters).await?;
+            let (register_ether_call, set_ether_metadata_call) =
+                commands::register_ether(context, &params.register_ether).await?;
+            let mut calls = Calls::bridge_hub(vec![
+                set_gateway_address(&params.gateway_address),
+                set_pricing_parameters,
+                gateway_operating_mode(&params.gateway_operating_mode.gateway_operating_mode),
+                force_checkpoint(context, &params.force_checkpoint).await?,
+            ]);
+            calls.extend(Calls::asset_hub(vec![
+                register_ether_call,
+                set_ether_metadata_call,
+                force_xcm_version(),
+                set_ethereum_fee,
+            ]));
+            calls
+        }
+        Command::GatewayOperatingMode(params) => {
+            Calls::bridge_hub(vec![gateway_operating_mode(&params.gateway_operating_mode)])
+        }
+        Command::Upgrade(params) => {
+            let initializer_params = initializer_params(params)?;
+            if let Some(ethereum_rpc) = &params.ethereum_rpc {
+                check_upgrade(context, params, &initializer_params, ethereum_rpc).await?;
+            }
+            Calls::bridge_hub(vec![upgrade(params, initializer_params)])
+        }
+        Command::PricingParameters(params) => {
+            let (set_pricing_parameters, set_ethereum_fee) =
+                pricing_parameters(context, params).await?;
+            let mut calls = Calls::bridge_hub(vec![set_pricing_parameters]);
+            calls.push_asset_hub(set_ethereum_fee);
+            calls
+        }
+        Command::TokenTransferFees(params) => Calls::bridge_hub(vec![token_transfer_fees(params)?]),
+        Command::HaltBridge(params) => {
+            let mut calls = Calls::default();
+            let mut halt_all = params.all;
+            // if no individual option specified, assume halt the whole bridge.
+            if !params.gateway
+                && !params.inbound_queue
+                && !params.outbound_queue
+                && !params.ethereum_client
+                && !params.assethub_max_fee
+            {
+                halt_all = true;
+            }
+            if params.gateway || halt_all {
+                calls.push_bridge_hub(gateway_operating_mode(
+                    &GatewayOperatingModeEnum::RejectingOutboundMessages,
+                ));
+            }
+            if params.inbound_queue || halt_all {
+                calls.push_bridge_hub(inbound_queue_operating_mode(&OperatingModeEnum::Halted));
+            }
+            if params.outbound_queue || halt_all {
+                calls.push_bridge_hub(outbound_queue_operating_mode(&OperatingModeEnum::Halted));
+            }
+            if params.ethereum_client || halt_all {
+                calls.push_bridge_hub(ethereum_client_operating_mode(&OperatingModeEnum::Halted));
+            }
+            if params.assethub_max_fee || halt_all {
+                calls.push_asset_hub(set_assethub_fee(u128::MAX));
+            }
+            calls
+        }
+        Command::ResumeBridge(params) => {
+            let mut calls = Calls::default();
+            let mut resume_all = params.all;
+            // if no individual option specified, assume resume the whole bridge.
+            if !params.gateway
+                && !params.inbound_queue
+                && !params.outbound_queue
+                && !params.ethereum_client
+                && !params.assethub_fee
+                && params.assethub_base_fee.is_none()
+            {
+                resume_all = true;
+            }
+            if params.gateway || resume_all {
+                calls.push_bridge_hub(gateway_operating_mode(&GatewayOperatingModeEnum::Normal));
+            }
+            if params.inbound_queue || resume_all {
+                calls.push_bridge_hub(inbound_queue_operating_mode(&OperatingModeEnum::Normal));
+            }
+            if params.outbound_queue || resume_all {
+                calls.push_bridge_hub(outbound_queue_operating_mode(&OperatingModeEnum::Normal));
+            }
+            if params.ethereum_client || resume_all {
+                calls.push_bridge_hub(ethereum_client_operating_mode(&OperatingModeEnum::Normal));
+            }
+            if params.assethub_fee || params.assethub_base_fee.is_some() || resume_all {
+                let fee = match &params.assethub_base_fee {
+                    Some(fee) => fee.planck(NETWORK)?,
+                    None => current_assethub_base_fee(context).await?,
+                };
+                eprintln!("AssetHub:");
+                eprintln!("  BaseFee: {}", native(fee));
+                provenance::record("AssetHub", "BaseFee", fee.to_string());
+                calls.push_asset_hub(set_assethub_fee(fee));
+            }
+            calls
+        }
+        Command::AgentTransfer(params) => {
+            Calls::bridge_hub(vec![agent_transfer(context, params).await?])
+        }
+        Command::RegisterPna(params) => Calls::bridge_hub(
+            super::native_assets::register_polkadot_native_assets(context, params).await?,
+        ),
+        _ => return Err("Not a Snowbridge command".into()),
+    };
+
+    Ok(calls)
+}
+
+pub fn gateway_operating_mode(operating_mode: &GatewayOperatingModeEnum) -> BridgeHubRuntimeCall {
+    let mode = match operating_mode {
+        GatewayOperatingModeEnum::Normal => OperatingMode::Normal,
+        GatewayOperatingModeEnum::RejectingOutboundMessages => {
+            OperatingMode::RejectingOutboundMessages
+        }
+    };
+    BridgeHubRuntimeCall::EthereumSystem(
+        snowbridge_pallet_system::pallet::Call::set_operating_mode { mode },
+    )
+}
+
+pub fn inbound_queue_operating_mode(param: &OperatingModeEnum) -> BridgeHubRuntimeCall {
+    let mode = match param {
+        OperatingModeEnum::Normal => BasicOperatingMode::Normal,
+        OperatingModeEnum::Halted => BasicOperatingMode::Halted,
+    };
+    BridgeHubRuntimeCall::EthereumInboundQueue(
+        snowbridge_pallet_inbound_queue::pallet::Call::set_operating_mode { mode },
+    )
+}
+
+pub fn ethereum_client_operating_mode(param: &OperatingModeEnum) -> BridgeHubRuntimeCall {
+    let mode = match param {
+        OperatingModeEnum::Normal => BasicOperatingMode::Normal,
+        OperatingModeEnum::Halted => BasicOperatingMode::Halted,
+    };
+    BridgeHubRuntimeCall::EthereumBeaconClient(
+        snowbridge_pallet_ethereum_client::pallet::Call::set_operating_mode { mode },
+    )
+}
+
+pub fn outbound_queue_operating_mode(param: &OperatingModeEnum) -> BridgeHubRuntimeCall {
+    let mode = match param {
+        OperatingModeEnum::Normal => BasicOperatingMode::Normal,
+        OperatingModeEnum::Halted => BasicOperatingMode::Halted,
+    };
+    BridgeHubRuntimeCall::EthereumOutboundQueue(
+        snowbridge_pallet_outbound_queue::pallet::Call::set_operating_mode { mode },
+    )
+}
+
+// Resolve the initializer params, encoding them from JSON if needed. Whenever the layout is
+// known, the final bytes are decoded again and printed for review.
+pub fn initializer_params(params: &UpgradeArgs) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let layout = match (
+        &params.initializer_layout.initializer_type,
+        &params.initializer_layout.initializer_abi,
+        &params.initializer_function,
+    ) {
+        (Some(signature), _, _) => Some(
+            ethereum::parse_layout(signature)
+                .map_err(|e| format!("Invalid initializer type: {e}"))?,
+        ),
+        (None, Some(path), Some(function)) => Some(ethereum::load_layout(path, function)?),
+        _ => None,
+    };
+
+    let data = match (
+        &params.initializer_params,
+        &params.initializer_json,
+        &layout,
+    ) {
+        (Some(data), _, _) => data.to_vec(),
+        (None, Some(value), Some(layout)) => ethereum::encode_params(layout, value)
+            .map_err(|e| format!("Failed to encode initializer params: {e}"))?,
+        _ => return Err("Initializer params are required".into()),
+    };
+
+    eprintln!("Initializer:");
+    eprintln!("  Params: 0x{}", hex::encode(&data));
+    provenance::record("Initializer", "Params", format!("0x{}", hex::encode(&data)));
+    if let Some(layout) = &layout {
+        let decoded = ethereum::decode_params(layout, &data)
+            .map_err(|e| format!("Failed to decode initializer params: {e}"))?;
+        eprintln!("  Decoded:");
+        eprint!("{}", ethereum::format_params(layout, &decoded, 4));
+    }
+
+    Ok(data)
+}
+
+pub fn upgrade(params: &UpgradeArgs, initializer_params: Vec<u8>) -> BridgeHubRuntimeCall {
+    BridgeHubRuntimeCall::EthereumSystem(snowbridge_pallet_system::pallet::Call::upgrade {
+        impl_address: params.logic_address.into_array().into(),
+        impl_code_hash: params.logic_code_hash.0.into(),
+        initializer: Some(Initializer {
+            params: initializer_params,
+            maximum_required_gas: params.initializer_gas,
+        }),
+    })
+}
+
+// Check the logic contract against an Ethereum node before proposing the upgrade. The initializer
+// is estimated in the storage context of the gateway proxy, as it would be when delegatecalled.
+pub async fn check_upgrade(
+    context: &Context,
+    params: &UpgradeArgs,
+    initializer_params: &[u8],
+    ethereum_rpc: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rpc = context.ethereum(ethereum_rpc).await?;
+
+    let code = rpc.get_code(params.logic_address).await?;
+    if code.is_empty() {
+        return Err(format!("No contract deployed at {}", params.logic_address).into());
+    }
+    let code_hash = keccak256(&code);
+
+    eprintln!("Logic Contract:");
+    eprintln!("  Address: {}", params.logic_address);
+    eprintln!("  Code Hash: {}", code_hash);
+    provenance::record("Logic Contract", "CodeHash", code_hash.to_string());
+    if code_hash != params.logic_code_hash {
+        return Err(format!(
+            "Code hash of {} is {}, expected {}",
+            params.logic_address, code_hash, params.logic_code_hash
+        )
+        .into());
+    }
+
+    let gateway_address =
+        query_gateway_address(&context.bridge_hub_api, context.bridge_hub_block.hash)
+            .await?
+            .ok_or("Gateway address is not set on BridgeHub")?;
+    let data = ethereum::encode_initialize(initializer_params);
+    let gas = rpc
+        .estimate_gas(gateway_address.0.into(), &data, Some(&code))
+        .await
+        .map_err(|e| format!("Failed to estimate initializer gas: {e}"))?
+        .saturating_sub(ethereum::intrinsic_gas(&data));
+
+    eprintln!("Initializer Gas:");
+    eprintln!("  Estimated: {}", gas);
+    eprintln!("  Maximum: {}", params.initializer_gas);
+    provenance::record("Initializer Gas", "Estimated", gas);
+    if gas > params.initializer_gas {
+        return Err(format!(
+            "Initializer requires an estimated {} gas, more than the maximum of {}",
+            gas, params.initializer_gas
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub async fn pricing_parameters(
+    context: &Context,
+    params: &PricingParametersArgs,
+) -> Result<(BridgeHubRuntimeCall, AssetHubRuntimeCall), Box<dyn std::error::Error>> {
+    let pricing_params = build_pricing_parameters(params)?;
+    let local_reward = pricing_params.rewards.local;
+
+    let total_outbound_fee_adjusted = assethub_base_fee(context, &pricing_params).await?;
+
+    eprintln!("BridgeHub:");
+    eprintln!(
+        "  ExchangeRate: {} ETH/{}",
+        params.exchange_rate_numerator as f64 / params.exchange_rate_denominator as f64,
+        NETWORK.symbol()
+    );
+    eprintln!("  FeePerGas: {}", gwei(params.fee_per_gas));
+    eprintln!("  LocalReward: {}", native(local_reward));
+    eprintln!("  RemoteReward: {}", ether(params.remote_reward));
+    eprintln!("AssetHub:");
+    eprintln!("  BaseFee: {}", native(total_outbound_fee_adjusted));
+
+    provenance::record(
+        "BridgeHub",
+        "ExchangeRate",
+        format!(
+            "{}/{}",
+            params.exchange_rate_numerator, params.exchange_rate_denominator
+        ),
+    );
+    provenance::record("BridgeHub", "FeePerGas", params.fee_per_gas.to_string());
+    provenance::record("BridgeHub", "LocalReward", local_reward.to_string());
+    provenance::record(
+        "BridgeHub",
+        "RemoteReward",
+        params.remote_reward.to_string(),
+    );
+    provenance::record(
+        "AssetHub",
+        "BaseFee",
+        total_outbound_fee_adjusted.to_string(),
+    );
+
+    Ok((
+        set_pricing_parameters(pricing_params),
+        set_assethub_fee(total_outbound_fee_adjusted),
+    ))
+}
+
+pub fn build_pricing_parameters(
+    params: &PricingParametersArgs,
+) -> Result<PricingParameters<u128>, Box<dyn std::error::Error>> {
+    let local_reward = params.local_reward.planck(NETWORK)?;
+
+    Ok(PricingParameters {
+        exchange_rate: Static(FixedU128::from_rational(
+            params.exchange_rate_numerator.into(),
+            params.exchange_rate_denominator.into(),
+        )),
+        multiplier: Static(FixedU128::from_rational(
+            params.multiplier_numerator.into(),
+            params.multiplier_denominator.into(),
+        )),
+        fee_per_gas: super::bridge_hub_runtime::runtime_types::primitive_types::U256(
+            params.fee_per_gas.into_limbs(),
+        ),
+        rewards: Rewards {
+            local: local_reward,
+            remote: super::bridge_hub_runtime::runtime_types::primitive_types::U256(
+                params.remote_reward.into_limbs(),
+            ),
+        },
+    })
+}
+
+pub fn set_pricing_parameters(params: PricingParameters<u128>) -> BridgeHubRuntimeCall {
+    BridgeHubRuntimeCall::EthereumSystem(
+        snowbridge_pallet_system::pallet::Call::set_pricing_parameters { params },
+    )
+}
+
+pub async fn assethub_base_fee(
+    context: &Context,
+    pricing_params: &PricingParameters<u128>,
+) -> Result<u128, Box<dyn std::error::Error>> {
+    let outbound_delivery_fee = calculate_delivery_fee(
+        &context.bridge_hub_api,
+        context.bridge_hub_block.hash,
+        pricing_params,
+    )
+    .await?;
+
+    let total_outbound_fee = outbound_delivery_fee.local + outbound_delivery_fee.remote;
+
+    // Adjust outbound fee up by 10% as a buffer
+    Ok(total_outbound_fee.saturating_add(total_outbound_fee / 10))
+}
+
+pub async fn current_assethub_base_fee(
+    context: &Context,
+) -> Result<u128, Box<dyn std::error::Error>> {
+    let pricing_params =
+        query_pricing_parameters(&context.bridge_hub_api, context.bridge_hub_block.hash).await?;
+    assethub_base_fee(context, &pricing_params).await
+}
+
+pub fn token_transfer_fees(
+    params: &TokenTransferFeesArgs,
+) -> Result<BridgeHubRuntimeCall, Box<dyn std::error::Error>> {
+    let create_asset_xcm = params.create_asset_xcm.planck(NETWORK)?;
+    let transfer_asset_xcm = params.transfer_asset_xcm.planck(NETWORK)?;
+
+    eprintln!("BridgeHub:");
+    eprintln!("  CreateAssetXcm: {}", native(create_asset_xcm));
+    eprintln!("  TransferAssetXcm: {}", native(transfer_asset_xcm));
+    eprintln!("  RegisterToken: {}", ether(params.register_token));
+    provenance::record("BridgeHub", "CreateAssetXcm", create_asset_xcm.to_string());
+    provenance::record(
+        "BridgeHub",
+        "TransferAssetXcm",
+        transfer_asset_xcm.to_string(),
+    );
+    provenance::record(
+        "BridgeHub",
+        "RegisterToken",
+        params.register_token.to_string(),
+    );
+
+    Ok(BridgeHubRuntimeCall::EthereumSystem(
+        snowbridge_pallet_system::pallet::Call::set_token_transfer_fees {
+            create_asset_xcm,
+            transfer_asset_xcm,
+            register_token: super::bridge_hub_runtime::runtime_types::primitive_types::U256(
+                params.register_token.into_limbs(),
+            ),
+        },
+    ))
+}
+
+pub fn set_assethub_fee(fee: u128) -> AssetHubRuntimeCall {
+    let asset_hub_outbound_fee_storage_key: Vec<u8> =
+        twox_128(b":BridgeHubEthereumBaseFee:").to_vec();
+    let asset_hub_outbound_fee_encoded: Vec<u8> = fee.encode();
+
+    eprintln!(
+        "Storage key for 'BridgeHubEthereumBaseFee': 0x{}",
+        hex::encode(&asset_hub_outbound_fee_storage_key)
+    );
+    provenance::record(
+        "AssetHub",
+        "BridgeHubEthereumBaseFee",
+        serde_json::json!({
+            "key": format!("0x{}", hex::encode(&asset_hub_outbound_fee_storage_key)),
+            "value": format!("0x{}", hex::encode(&asset_hub_outbound_fee_encoded)),
+        }),
+    );
+
+    AssetHubRuntimeCall::System(
+        super::asset_hub_runtime::runtime_types::frame_system::pallet::Call::set_storage {
+            items: vec![(
+                asset_hub_outbound_fee_storage_key,
+                asset_hub_outbound_fee_encoded,
+            )],
+        },
+    )
+}
+
+pub async fn load_checkpoint(
+    context: &Context,
+    params: &ForceCheckpointArgs,
+) -> Result<CheckpointUpdate, Box<dyn std::error::Error>> {
+    let checkpoint = match (&params.checkpoint, &params.beacon_url) {
+        (Some(path), _) => {
+            let data = std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+            serde_json::from_str(&data)
+                .map_err(|e| format!("Invalid checkpoint {}: {e}", path.display()))?
+        }
+        (None, Some(beacon_url)) => {
+            // Without a slot or block root, use the same finalized block for the whole build
+            let block_root = match (params.block_root, params.slot) {
+                (None, None) => Some(context.beacon_block_root(beacon_url).await?),
+                (block_root, _) => block_root,
+            };
+            beacon::fetch_checkpoint(beacon_url, params.slot, block_root).await?
+        }
+        (None, None) => return Err("Either a checkpoint file or beacon URL is required".into()),
+    };
+    Ok(checkpoint)
+}
+
+pub async fn verify_checkpoint(
+    context: &Context,
+    params: &ForceCheckpointArgs,
+    checkpoint: &CheckpointUpdate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    beacon::verify_checkpoint(checkpoint)?;
+
+    let epoch = checkpoint.header.slot / beacon::SLOTS_PER_EPOCH;
+    let block_root = checkpoint
+        .header
+        .hash_tree_root()
+        .map_err(|e| format!("Failed to hash the checkpoint header: {e:?}"))?;
+
+    eprintln!("Checkpoint:");
+    eprintln!("  Block Root: {:?}", block_root);
+    eprintln!("  Slot: {}", checkpoint.header.slot);
+    eprintln!("  Epoch: {}", epoch);
+    eprintln!("  Merkle Branches: valid");
+    provenance::record("Checkpoint", "BlockRoot", format!("{:?}", block_root));
+    provenance::record("Checkpoint", "Slot", checkpoint.header.slot);
+    provenance::record("Checkpoint", "Epoch", epoch);
+
+    if let Some(finalized_slot) =
+        query_finalized_beacon_slot(&context.bridge_hub_api, context.bridge_hub_block.hash).await?
+    {
+        let finalized_epoch = finalized_slot / beacon::SLOTS_PER_EPOCH;
+        eprintln!("  BridgeHub Finalized Epoch: {}", finalized_epoch);
+        if epoch.saturating_add(params.weak_subjectivity_epochs) < finalized_epoch {
+            eprintln!(
+                "WARNING: Checkpoint is {} epochs older than the finalized beacon state on \
+                 BridgeHub, which exceeds the weak subjectivity window of {} epochs",
+                finalized_epoch - epoch,
+                params.weak_subjectivity_epochs
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// Print the state of the bridge, with amounts in the units of the commands setting them
+pub async fn status(
+    context: &Context,
+    params: &StatusArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let api = &context.bridge_hub_api;
+    let at = context.bridge_hub_block.hash;
+    let modes = query_operating_modes(api, at).await?;
+    let pricing_params = query_pricing_parameters(api, at).await?;
+    let gateway_address = query_gateway_address(api, at).await?;
+    let checkpoint = query_finalized_beacon_checkpoint(api, at).await?;
+    let base_fee =
+        query_assethub_base_fee(&context.asset_hub_api, context.asset_hub_block.hash).await?;
+
+    let fee_per_gas = U256::from_limbs(pricing_params.fee_per_gas.0);
+    let remote_reward = U256::from_limbs(pricing_params.rewards.remote.0);
+
+    println!(
+        "BridgeHub (#{} {:?}):",
+        context.bridge_hub_block.number, context.bridge_hub_block.hash
+    );
+    println!("  InboundQueue: {:?}", modes.inbound_queue);
+    println!("  OutboundQueue: {:?}", modes.outbound_queue);
+    println!("  EthereumBeaconClient: {:?}", modes.ethereum_client);
+    println!(
+        "  ExchangeRate: {}",
+        exchange_rate(pricing_params.exchange_rate.0)
+    );
+    println!("  Multiplier: {}", fixed(pricing_params.multiplier.0));
+    println!("  FeePerGas: {}", gwei(fee_per_gas));
+    println!("  LocalReward: {}", native(pricing_params.rewards.local));
+    println!("  RemoteReward: {}", ether(remote_reward));
+    match gateway_address {
+        Some(address) => println!(
+            "  EthereumGatewayAddress: {}",
+            Address::from(address.0).to_checksum(None)
+        ),
+        None => println!("  EthereumGatewayAddress: not set"),
+    }
+    match checkpoint {
+        Some((block_root, slot)) => {
+            println!("  Finalized Checkpoint:");
+            println!("    Block Root: {:?}", block_root);
+            println!("    Slot: {}", slot);
+            println!("    Epoch: {}", slot / beacon::SLOTS_PER_EPOCH);
+        }
+        None => println!("  Finalized Checkpoint: not initialized"),
+    }
+
+    println!(
+        "AssetHub (#{} {:?}):",
+        context.asset_hub_block.number, context.asset_hub_block.hash
+    );
+    match base_fee {
+        Some(fee) => println!("  BridgeHubEthereumBaseFee: {}", native(fee)),
+        None => println!("  BridgeHubEthereumBaseFee: not set"),
+    }
+
+    // The Gateway operating mode and token transfer fees are sent to Ethereum by EthereumSystem,
+    // which does not store them on BridgeHub
+    let (Some(ethereum_rpc), Some(gateway_address)) = (&params.ethereum_rpc, gateway_address)
+    else {
+        println!("Gateway: skipped, requires --ethereum-rpc and the gateway address");
+        return Ok(());
+    };
+    let rpc = context.ethereum(ethereum_rpc).await?;
+    let gateway = Address::from(gateway_address.0);
+    let mode = ethereum::gateway_operating_mode(&rpc, gateway).await?;
+    let (create_asset_xcm, transfer_asset_xcm, register_token) =
+        ethereum::gateway_token_transfer_fees(&rpc, gateway).await?;
+
+    println!("Gateway ({}):", gateway.to_checksum(None));
+    match mode {
+        0 => println!("  OperatingMode: Normal"),
+        1 => println!("  OperatingMode: RejectingOutboundMessages"),
+        mode => println!("  OperatingMode: unknown ({mode})"),
+    }
+    println!("  CreateAssetXcm: {}", native(create_asset_xcm));
+    println!("  TransferAssetXcm: {}", native(transfer_asset_xcm));
+    println!("  RegisterToken: {}", ether(register_token));
+
+    Ok(())
+}
+
+pub async fn force_checkpoint(
+    context: &Context,
+    params: &ForceCheckpointArgs,
+) -> Result<BridgeHubRuntimeCall, Box<dyn std::error::Error>> {
+    let checkpoint = load_checkpoint(context, params).await?;
+    verify_checkpoint(context, params, &checkpoint).await?;
+    Ok(BridgeHubRuntimeCall::EthereumBeaconClient(
+        snowbridge_pallet_ethereum_client::pallet::Call::force_checkpoint {
+            update: Box::new(Static(checkpoint)),
+        },
+    ))
+}
+
+pub fn set_gateway_address(params: &GatewayAddressArgs) -> BridgeHubRuntimeCall {
+    let storage_key = sp_crypto_hashing::twox_128(b":EthereumGatewayAddress:").to_vec();
+    let storage_value = params.gateway_address.into_array().encode();
+    provenance::record(
+        "BridgeHub",
+        "EthereumGatewayAddress",
+        serde_json::json!({
+            "key": format!("0x{}", hex::encode(&storage_key)),
+            "value": format!("0x{}", hex::encode(&storage_value)),
+        }),
+    );
+    BridgeHubRuntimeCall::System(
+        super::bridge_hub_runtime::runtime_types::frame_system::pallet::Call::set_storage {
+            items: vec![(storage_key, storage_value)],
+        },
+    )
+}
+
+// Re-encode a location into the BridgeHub runtime types
+pub fn bridge_hub_location(
+    location: &xcm::v4::Location,
+) -> Result<super::bridge_hub_runtime::runtime_types::xcm::VersionedLocation, String> {
+    let encoded = xcm::VersionedLocation::V4(location.clone()).encode();
+    super::bridge_hub_runtime::runtime_types::xcm::VersionedLocation::decode(
+        &mut encoded.as_slice(),
+    )
+    .map_err(|e| format!("location is not supported by BridgeHub: {e}"))
+}
+
+pub async fn agent_transfer(
+    context: &Context,
+    params: &AgentTransferArgs,
+) -> Result<BridgeHubRuntimeCall, Box<dyn std::error::Error>> {
+    let location = match (&params.location, &params.agent_id) {
+        (Some(location), _) => location.clone(),
+        (None, Some(agent_id)) => {
+            let para_id = query_agent_para_id(
+                &context.bridge_hub_api,
+                context.bridge_hub_block.hash,
+                agent_id.0.into(),
+            )
+            .await?
+            .ok_or(format!("No channel found for agent {agent_id}"))?;
+            xcm::v4::Location::new(1, [xcm::v4::Junction::Parachain(para_id)])
+        }
+        (None, None) => return Err("Either an agent location or agent ID is required".into()),
+    };
+    let amount: u128 = params
+        .amount
+        .try_into()
+        .map_err(|_| "Amount does not fit in u128")?;
+
+    eprintln!("BridgeHub:");
+    eprintln!("  Agent: {:?}", location);
+    eprintln!("  Recipient: {}", params.recipient);
+    eprintln!("  Amount: {}", ether(params.amount));
+    provenance::record("BridgeHub", "Agent", format!("{:?}", location));
+    provenance::record("BridgeHub", "Amount", params.amount.to_string());
+
+    Ok(BridgeHubRuntimeCall::EthereumSystem(
+        snowbridge_pallet_system::pallet::Call::force_transfer_native_from_agent {
+            location: Box::new(bridge_hub_location(&location)?),
+            recipient: params.recipient.into_array().into(),
+            amount,
+        },
+    ))
+}
+
+pub fn force_xcm_version() -> AssetHubRuntimeCall {
+    let location = Box::new(super::asset_hub_types::get_ethereum_location(
+        NETWORK.chain_id(),
+    ));
+
+    AssetHubRuntimeCall::PolkadotXcm(
+        super::asset_hub_runtime::runtime_types::pallet_xcm::pallet::Call::force_xcm_version {
+            location,
+            version: NETWORK.xcm_version(),
+        },
+    )
+}
@@ -0,0 +1,147 @@
+use crate::{
+    build, connect, decoder, diff, provenance, ApiEndpoints, Cli, Command, PinnedBlocks, Proposal,
+    VerifyArgs,
+};
+use alloy_primitives::FixedBytes;
+use clap::Parser;
+use sp_crypto_hashing::blake2_256;
+
+/// Regenerate the preimage of a JSON manifest with its original arguments, querying the chains,
+/// Ethereum and the beacon chain at the blocks it was built at, and check that it matches the
+/// preimage in the manifest byte for byte, and the expected hash
+pub async fn verify(cli: &Cli, params: &VerifyArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let path = &params.manifest;
+    let manifest: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?,
+    )
+    .map_err(|e| format!("Invalid manifest {}: {e}", path.display()))?;
+
+    let arguments: Vec<String> = serde_json::from_value(manifest["tool"]["arguments"].clone())
+        .map_err(|e| format!("Missing arguments in the manifest: {e}"))?;
+    let original =
+        Cli::try_parse_from(std::iter::once("snowbridge-preimage".into()).chain(arguments))
+            .map_err(|e| format!("Invalid arguments in the manifest: {e}"))?;
+    if let Command::Decode(_) | Command::Diff(_) | Command::Verify(_) = original.command {
+        return Err("The manifest was not printed for a preimage".into());
+    }
+
+    if original.network != cli.network {
+        return Err(format!("The manifest is for {}", original.network).into());
+    }
+
+    let revision = manifest["tool"]["revision"].as_str().unwrap_or("unknown");
+    if revision != provenance::REVISION {
+        eprintln!(
+            "WARNING: The preimage was built with revision {revision}, and is verified with {}",
+            provenance::REVISION
+        );
+    }
+
+    // Endpoints given to verify, such as archive nodes, take precedence over the original ones
+    let endpoints = ApiEndpoints {
+        bridge_hub_api: cli
+            .api_endpoints
+            .bridge_hub_api
+            .clone()
+            .or(original.api_endpoints.bridge_hub_api.clone()),
+        asset_hub_api: cli
+            .api_endpoints
+            .asset_hub_api
+            .clone()
+            .or(original.api_endpoints.asset_hub_api.clone()),
+        relay_api: cli
+            .api_endpoints
+            .relay_api
+            .clone()
+            .or(original.api_endpoints.relay_api.clone()),
+    };
+    let pinned_blocks = PinnedBlocks {
+        bridge_hub_at: Some(block_hash(&manifest, "bridge_hub")?),
+        asset_hub_at: Some(block_hash(&manifest, "asset_hub")?),
+        relay_at: Some(block_hash(&manifest, "relay")?),
+        ethereum_at: manifest["ethereum"]["block"].as_u64(),
+        beacon_at: manifest["beacon"]["block_root"]
+            .as_str()
+            .map(|block_root| block_root.parse())
+            .transpose()
+            .map_err(|_| "Invalid beacon block root in the manifest")?,
+    };
+
+    let context = connect(original.network, &endpoints, &pinned_blocks).await?;
+    let Proposal { preimage, .. } = build(&original, &context).await?;
+
+    // A preimage built from the latest Ethereum or beacon block can't be regenerated from the
+    // same state unless the manifest records it
+    if pinned_blocks.ethereum_at.is_none()
+        && context
+            .ethereum_block
+            .lock()
+            .expect("not poisoned")
+            .is_some()
+    {
+        return Err(
+            "The manifest does not record the Ethereum block the preimage was built at".into(),
+        );
+    }
+    if pinned_blocks.beacon_at.is_none()
+        && context
+            .beacon_block_root
+            .lock()
+            .expect("not poisoned")
+            .is_some()
+    {
+        return Err(
+            "The manifest does not record the beacon block the preimage was built at".into(),
+        );
+    }
+
+    let expected = hex_field(&manifest, "preimage")?;
+    let expected_hash = match params.hash {
+        Some(hash) => hash,
+        None => FixedBytes(
+            hex_field(&manifest, "hash")?
+                .try_into()
+                .map_err(|_| "Invalid hash in the manifest")?,
+        ),
+    };
+    let hash = blake2_256(&preimage);
+    eprintln!("Preimage Hash: 0x{}", hex::encode(hash));
+    eprintln!("Preimage Size: {}", preimage.len());
+
+    if preimage != expected {
+        let decoder = decoder(original.network)?;
+        let changes = diff::diff(&decoder.decode(&expected)?, &decoder.decode(&preimage)?);
+        eprint!("{}", diff::text(&changes, false));
+        return Err("Regenerated preimage does not match the manifest".into());
+    }
+    if hash != expected_hash.0 {
+        return Err(
+            format!("Preimage hash does not match the expected hash {expected_hash}").into(),
+        );
+    }
+
+    eprintln!("Preimage matches");
+    Ok(())
+}
+
+fn block_hash(
+    manifest: &serde_json::Value,
+    chain: &str,
+) -> Result<FixedBytes<32>, Box<dyn std::error::Error>> {
+    manifest["runtimes"][chain]["block"]["hash"]
+        .as_str()
+        .and_then(|hash| hash.parse().ok())
+        .ok_or_else(|| format!("Missing {chain} block hash in the manifest").into())
+}
+
+fn hex_field(
+    manifest: &serde_json::Value,
+    name: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let value = manifest[name]
+        .as_str()
+        .and_then(|value| value.strip_prefix("0x"))
+        .ok_or(format!("Missing {name} in the manifest"))?;
+    Ok(hex::decode(value)?)
+}
@@ -5,3 +5,6 @@
 mod runtime {}
 
 pub use runtime::*;
+
+/// SCALE-encoded metadata the runtime types were generated from
+pub const METADATA: &[u8] = include_bytes!("../asset-hub-metadata.bin");
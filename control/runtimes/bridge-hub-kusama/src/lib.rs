@@ -10,4 +10,7 @@ mod runtime {}
 
 pub use runtime::*;
 
+/// SCALE-encoded metadata the runtime types were generated from
+pub const METADATA: &[u8] = include_bytes!("../bridge-hub-metadata.bin");
+
 pub const CHAIN_ID: u64 = 1;